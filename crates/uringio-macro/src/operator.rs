@@ -1,4 +1,5 @@
 pub mod attr;
+pub mod flag;
 pub mod item;
 pub mod setter;
 
@@ -7,7 +8,7 @@ use syn::__private::{
     quote::{ToTokens, quote},
 };
 
-use crate::operator::{attr::Attr, item::Item};
+use crate::operator::{attr::Attr, flag::gen_flag_setters, item::Item};
 
 #[derive(Debug)]
 pub struct Operator {
@@ -26,11 +27,13 @@ impl Operator {
 
         let impl_op = self.attr.gen_impl_trait_op(name, &generics);
         let impl_setters = self.item.gen_fn_setter_methods();
+        let impl_flag_setters = gen_flag_setters(name, &generics, &self.item.fields);
         let test_size_align = self.attr.gen_test_size_align(name);
 
         quote! {
             #impl_op
             #impl_setters
+            #impl_flag_setters
             #test_size_align
         }
     }
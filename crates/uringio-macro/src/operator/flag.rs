@@ -0,0 +1,61 @@
+use syn::{
+    __private::{
+        Span, TokenStream2,
+        quote::{ToTokens, quote},
+    },
+    Fields, Ident,
+};
+
+/// `IoUringSqeFlags` setters shared by every operator
+const FLAG_SETTERS: [(&str, &str); 5] = [
+    ("io_link", "IO_LINK"),
+    ("io_hardlink", "IO_HARDLINK"),
+    ("io_drain", "IO_DRAIN"),
+    ("force_async", "ASYNC"),
+    ("skip_cqe", "CQE_SKIP_SUCCESS"),
+];
+
+/// Setter only generated for operators with a `buf_group` field
+const BUFFER_SELECT: (&str, &str) = ("buffer_select", "BUFFER_SELECT");
+
+#[derive(Debug)]
+pub struct FlagSetter {
+    pub fn_name: Ident,
+    pub flag: Ident,
+}
+
+impl ToTokens for FlagSetter {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let fn_name = &self.fn_name;
+        let flag = &self.flag;
+
+        let token = quote! {
+            #[inline]
+            pub fn #fn_name(mut self) -> Self {
+                self.flags |= IoUringSqeFlags::#flag;
+                self
+            }
+        };
+        tokens.extend(token);
+    }
+}
+
+fn has_buf_group(fields: &Fields) -> bool {
+    fields.iter().any(|field| field.ident.as_ref().is_some_and(|ident| ident == "buf_group"))
+}
+
+pub fn gen_flag_setters(name: &Ident, generics: &TokenStream2, fields: &Fields) -> TokenStream2 {
+    let buffer_select = has_buf_group(fields).then_some(BUFFER_SELECT);
+    let setters = FLAG_SETTERS.into_iter().chain(buffer_select).map(|(fn_name, flag)| {
+        FlagSetter {
+            fn_name: Ident::new(fn_name, Span::call_site()),
+            flag: Ident::new(flag, Span::call_site()),
+        }
+    });
+
+    quote! {
+        impl #name #generics {
+            #(#setters)*
+        }
+    }
+}
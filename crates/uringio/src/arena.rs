@@ -1,7 +1,11 @@
 pub mod huge;
 pub mod mmap;
 
-use crate::platform::mmap::Ptr;
+use crate::{
+    platform::mmap::Ptr,
+    shared::error::Result,
+    uring::{args::UringArgs, enter::UringEnter},
+};
 
 pub trait Arena<M, S, C>: Sized {
     fn sq(&self) -> Ptr;
@@ -9,4 +13,14 @@ pub trait Arena<M, S, C>: Sized {
     fn sqes(&self) -> Ptr;
 
     fn cq(&self) -> Ptr;
+
+    /// Map memory handed to `IORING_REGISTER_RESIZE_RINGS`, `None` if the kernel allocates it
+    fn prepare_resize(args: &mut UringArgs<Self, M, S, C>) -> Result<Option<Self>>;
+
+    /// Map the resized rings after the kernel switched to them
+    fn finish_resize(
+        enter: &UringEnter<'_, Self, M, S, C>,
+        args: &UringArgs<Self, M, S, C>,
+        arena: Option<Self>,
+    ) -> Result<Self>;
 }
@@ -15,7 +15,7 @@ use crate::{
         log::debug,
     },
     submission::entry::Sqe,
-    uring::{args::UringArgs, mode::Mode},
+    uring::{args::UringArgs, enter::UringEnter, mode::Mode},
 };
 
 // TODO: use platform huge page size
@@ -116,4 +116,20 @@ where
     fn cq(&self) -> Ptr {
         self.ring_mmap.ptr()
     }
+
+    fn prepare_resize(args: &mut UringArgs<Self, M, S, C>) -> Result<Option<Self>> {
+        // set new user addr before resize
+        Self::setup(args).map(Some)
+    }
+
+    fn finish_resize(
+        _enter: &UringEnter<'_, Self, M, S, C>,
+        _args: &UringArgs<Self, M, S, C>,
+        arena: Option<Self>,
+    ) -> Result<Self> {
+        match arena {
+            Some(arena) => Ok(arena),
+            None => err!("HugeArena resize without prepared memory"),
+        }
+    }
 }
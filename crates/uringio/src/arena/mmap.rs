@@ -9,7 +9,7 @@ use crate::{
     },
     shared::{error::Result, log::debug},
     submission::entry::Sqe,
-    uring::args::UringArgs,
+    uring::{args::UringArgs, enter::UringEnter},
 };
 
 /// ## Mmap Arena
@@ -57,4 +57,17 @@ where
     fn cq(&self) -> Ptr {
         self.ring_mmap.ptr()
     }
+
+    fn prepare_resize(_args: &mut UringArgs<Self, M, S, C>) -> Result<Option<Self>> {
+        Ok(None)
+    }
+
+    fn finish_resize(
+        enter: &UringEnter<'_, Self, M, S, C>,
+        args: &UringArgs<Self, M, S, C>,
+        _arena: Option<Self>,
+    ) -> Result<Self> {
+        // NOTE: new rings are only mappable after the kernel switched to them
+        Self::new(&enter.ring_fd(), args)
+    }
 }
@@ -1,3 +1,5 @@
 pub mod collector;
 pub mod entry;
 pub mod queue;
+pub mod registry;
+pub mod result;
@@ -1,12 +1,20 @@
-use std::sync::{atomic, atomic::Ordering};
+use std::{
+    sync::{atomic, atomic::Ordering},
+    time::Duration,
+};
 
 use crate::{
-    completion::queue::CompletionQueue,
+    completion::{
+        entry::{CqeMix, FixCqe, MixCqe},
+        queue::CompletionQueue,
+        result::Completion,
+    },
     platform::iouring::{IoUringEnterFlags, IoUringSqFlags},
-    shared::error::Result,
+    shared::{error::Result, log::debug},
     uring::{
         enter::UringEnter,
-        mode::{Mode, Sqpoll},
+        mode::{Mode, Sqpoll, Ty},
+        wait::{WaitArg, WaitStatus},
     },
 };
 
@@ -31,7 +39,8 @@ where
 
     #[inline]
     pub fn update_tail(&mut self) {
-        self.tail = self.queue.tail();
+        // Stale ring after a failed resize, completions are not reachable
+        self.tail = if self.queue.poisoned { self.head } else { self.queue.tail() };
     }
 
     pub fn update(&mut self) {
@@ -50,6 +59,114 @@ where
     }
 }
 
+impl<'fd, A, M, S, C> Collector<'_, 'fd, A, M, S, C>
+where
+    M: Mode,
+{
+    pub(crate) fn wait_impl(
+        &mut self,
+        enter: &mut UringEnter<'fd, A, M, S, C>,
+        to_submit: u32,
+        min_complete: u32,
+        flags: IoUringEnterFlags,
+        arg: &WaitArg<'_>,
+    ) -> Result<WaitStatus> {
+        // Kernel counts from the published head
+        self.update_head();
+        if matches!(M::TYPE, Ty::Iopoll) {
+            return self.poll_impl(enter, to_submit, min_complete, flags, arg);
+        }
+
+        let status = enter.enter_wait(to_submit, min_complete, flags, arg)?;
+        self.update_tail();
+
+        // NOTE: submitting masks ETIME
+        if self.size() < arg.ready_count(min_complete) {
+            return Ok(WaitStatus::TimedOut);
+        }
+
+        Ok(status)
+    }
+
+    /// IOPOLL enter never sleeps and ignores the timeout, poll until ready or deadline
+    fn poll_impl(
+        &mut self,
+        enter: &mut UringEnter<'fd, A, M, S, C>,
+        mut to_submit: u32,
+        min_complete: u32,
+        flags: IoUringEnterFlags,
+        arg: &WaitArg<'_>,
+    ) -> Result<WaitStatus> {
+        // No deadline on overflow, same as waiting forever
+        let deadline = match arg.ts {
+            Some(ts) if arg.abs => Some(ts),
+            Some(ts) => enter.now().checked_add(ts),
+            None => None,
+        };
+
+        loop {
+            enter.enter_wait(to_submit, min_complete, flags, arg)?;
+            self.update_tail();
+            to_submit = 0;
+
+            if self.size() >= arg.ready_count(min_complete) {
+                return Ok(WaitStatus::Ready);
+            }
+            // Poisoned queue never fills, same as a sleeping wait on the stale ring
+            if self.queue.poisoned || deadline.is_some_and(|deadline| enter.now() >= deadline) {
+                return Ok(WaitStatus::TimedOut);
+            }
+        }
+    }
+
+    /// Flush kernel overflow list into released CQ space, returns true if overflowed
+    pub fn flush_overflow(&mut self, enter: &mut UringEnter<'fd, A, M, S, C>) -> Result<bool> {
+        if !self.queue.cq_overflow() {
+            return Ok(false);
+        }
+
+        debug!("flush cq overflow");
+        self.update_head();
+        // IORING_ENTER_GETEVENTS call io_cqring_do_overflow_flush()
+        enter.enter(0, 0, IoUringEnterFlags::GETEVENTS)?;
+        self.queue.flushes += 1;
+        self.update_tail();
+
+        Ok(true)
+    }
+
+    /// Wait for `min_complete` completions up to `timeout`
+    pub fn wait(
+        &mut self,
+        enter: &mut UringEnter<'fd, A, M, S, C>,
+        min_complete: u32,
+        timeout: Duration,
+    ) -> Result<WaitStatus> {
+        self.wait_arg(enter, min_complete, &WaitArg::new().timeout(timeout))
+    }
+
+    /// Wait for `min_complete` up to `min_wait`, then for any completion up to `timeout`
+    pub fn wait_batch(
+        &mut self,
+        enter: &mut UringEnter<'fd, A, M, S, C>,
+        min_complete: u32,
+        timeout: Duration,
+        min_wait: Duration,
+    ) -> Result<WaitStatus> {
+        let arg = WaitArg::new().timeout(timeout).min_wait(min_wait);
+        self.wait_arg(enter, min_complete, &arg)
+    }
+
+    pub fn wait_arg(
+        &mut self,
+        enter: &mut UringEnter<'fd, A, M, S, C>,
+        min_complete: u32,
+        arg: &WaitArg<'_>,
+    ) -> Result<WaitStatus> {
+        self.wait_impl(enter, 0, min_complete, IoUringEnterFlags::empty(), arg)
+    }
+}
+
 impl<A, M, S, C> Drop for Collector<'_, '_, A, M, S, C>
 where
     M: Mode,
@@ -62,6 +179,7 @@ where
 impl<'c, A, M, S, C> Iterator for Collector<'c, '_, A, M, S, C>
 where
     M: Mode,
+    C: FixCqe,
 {
     type Item = &'c C;
 
@@ -86,6 +204,7 @@ where
 impl<A, M, S, C> ExactSizeIterator for Collector<'_, '_, A, M, S, C>
 where
     M: Mode,
+    C: FixCqe,
 {
     #[inline]
     fn len(&self) -> usize {
@@ -94,6 +213,46 @@ where
     }
 }
 
+// Cqe32 take 2 slots on CqeMix queue
+impl<'c, A, M, S> Iterator for Collector<'c, '_, A, M, S, CqeMix>
+where
+    M: Mode,
+{
+    type Item = MixCqe<'c>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.head != self.tail {
+            let cqe = self.queue.get_cqe(self.head);
+            // SAFETY: head < tail
+            let mix = unsafe { cqe.as_ref() };
+
+            if mix.is_skip() {
+                self.head = self.head.wrapping_add(1);
+                continue;
+            }
+
+            // SAFETY: kernel never splits a Cqe32 across ring wrap
+            return Some(
+                if mix.is_cqe32() {
+                    self.head = self.head.wrapping_add(2);
+                    MixCqe::Cqe32(unsafe { cqe.cast().as_ref() })
+                } else {
+                    self.head = self.head.wrapping_add(1);
+                    MixCqe::Cqe16(unsafe { cqe.cast().as_ref() })
+                },
+            );
+        }
+
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        #![allow(clippy::as_conversions)]
+        (0, Some(self.size() as usize))
+    }
+}
+
 impl<'fd, A, S, C> Collector<'_, 'fd, A, Sqpoll, S, C> {
     pub fn flush(
         &mut self,
@@ -121,8 +280,145 @@ impl<'fd, A, S, C> Collector<'_, 'fd, A, Sqpoll, S, C> {
         if enter_getevents {
             // IORING_ENTER_GETEVENTS call io_cqring_do_overflow_flush()
             flags.insert(IoUringEnterFlags::GETEVENTS);
+            if cq_overflow {
+                self.queue.flushes += 1;
+            }
         }
 
         enter.enter(0, min_complete, flags)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+    use crate::{
+        completion::{entry::Cqe16, queue::OverflowStats},
+        operator::noop::Nop,
+        platform::iouring::IoUringFeatureFlags,
+        shared::macros::test_uring,
+        submission::{entry::Sqe64, submitter::Submit},
+        uring::mode::Iopoll,
+    };
+
+    #[test]
+    fn test_collect_mix() {
+        test_uring!(uring = Iopoll::new::<Sqe64, CqeMix>(4).cqsize(8));
+
+        // 2nd round wraps: Cqe16 at slot 6, skip at slot 7, Cqe32 at slot 0
+        for round in [&[1, 2, 3, 4][..], &[5, 6]] {
+            let (enter, mut submitter, mut collector) = uring.borrow();
+            for &user_data in round {
+                let nop = Nop::new().user_data(user_data);
+                let nop = if user_data % 2 == 0 { nop.set_ext_data([user_data, 0]) } else { nop };
+                submitter.push(nop).unwrap();
+            }
+            submitter.submit(enter, u32::try_from(round.len()).unwrap()).unwrap();
+
+            collector.update_tail();
+            for &user_data in round {
+                let cqe = collector.next().unwrap();
+                assert_eq!(cqe.user_data.u64_(), user_data);
+                assert_eq!(cqe.res, 0);
+                assert_eq!(cqe.ext_data().is_some(), user_data % 2 == 0);
+                if let Some(ext_data) = cqe.ext_data() {
+                    assert_eq!(ext_data, &[user_data, 0]);
+                }
+            }
+            assert!(collector.next().is_none());
+        }
+    }
+
+    #[test]
+    fn test_wait_timeout() {
+        test_uring!(uring = Sqpoll::new::<Sqe64, Cqe16>(8));
+
+        {
+            let (enter, _, mut collector) = uring.borrow();
+            let start = Instant::now();
+            let status = collector.wait(enter, 1, Duration::from_millis(10)).unwrap();
+            assert_eq!(status, WaitStatus::TimedOut);
+            assert!(start.elapsed() >= Duration::from_millis(10));
+        }
+
+        uring.submitter().push(Nop::new().user_data(1)).unwrap();
+        let status = uring.submit_and_wait_timeout(1, Duration::from_secs(1)).unwrap();
+        assert_eq!(status, WaitStatus::Ready);
+        assert_eq!(uring.collector().next().map(|cqe| cqe.user_data.u64_()), Some(1));
+    }
+
+    #[test]
+    fn test_submit_and_wait_iopoll() {
+        test_uring!(uring = Iopoll::new::<Sqe64, Cqe16>(8));
+
+        let start = Instant::now();
+        let status = uring.submit_and_wait_timeout(1, Duration::from_millis(10)).unwrap();
+        assert_eq!(status, WaitStatus::TimedOut);
+        assert!(start.elapsed() >= Duration::from_millis(10));
+
+        {
+            let mut submitter = uring.submitter();
+            submitter.push(Nop::new()).unwrap();
+            submitter.push(Nop::new()).unwrap();
+        }
+        let status = uring.submit_and_wait_timeout(2, Duration::from_secs(1)).unwrap();
+        assert_eq!(status, WaitStatus::Ready);
+        assert_eq!(uring.collector().len(), 2);
+    }
+
+    #[test]
+    fn test_wait_batch() {
+        test_uring!(uring = Sqpoll::new::<Sqe64, Cqe16>(8));
+        assert!(uring.enter.features().contains(IoUringFeatureFlags::MIN_TIMEOUT));
+
+        let (enter, mut submitter, mut collector) = uring.borrow();
+        let (timeout, min_wait) = (Duration::from_secs(1), Duration::from_millis(10));
+        submitter.push(Nop::new().user_data(1)).unwrap();
+        submitter.submit(enter).unwrap();
+
+        let start = Instant::now();
+        let status = collector.wait_batch(enter, 4, timeout, min_wait).unwrap();
+        assert_eq!(status, WaitStatus::Ready);
+        assert!(start.elapsed() < timeout);
+        assert_eq!(collector.next().map(|cqe| cqe.user_data.u64_()), Some(1));
+
+        let status = collector.wait_batch(enter, 4, Duration::from_millis(20), min_wait).unwrap();
+        assert_eq!(status, WaitStatus::TimedOut);
+    }
+
+    #[test]
+    fn test_overflow_flush() {
+        test_uring!(uring = Iopoll::new::<Sqe64, Cqe16>(2).cqsize(2));
+
+        for user_data in [[1, 2], [3, 4]] {
+            let (enter, mut submitter, _) = uring.borrow();
+            for user_data in user_data {
+                submitter.push(Nop::new().user_data(user_data)).unwrap();
+            }
+            submitter.submit(enter, 0).unwrap();
+        }
+
+        // Throttled until overflow list flushed
+        assert!(uring.cq.cq_overflow());
+        assert!(uring.submitter().push(Nop::new()).is_err());
+        // Backlogged push is not refused
+        uring.sq.enable_backlog();
+        assert!(uring.submitter().push(Nop::new()).is_ok());
+        assert_eq!(uring.sq.throttled(), 1);
+
+        let (enter, _, mut collector) = uring.borrow();
+        collector.update_tail();
+        let mut user_datas: Vec<_> = collector.by_ref().map(|cqe| cqe.user_data.u64_()).collect();
+        assert!(collector.flush_overflow(enter).unwrap());
+        user_datas.extend(collector.by_ref().map(|cqe| cqe.user_data.u64_()));
+        assert_eq!(user_datas, [1, 2, 3, 4]);
+        assert!(!collector.flush_overflow(enter).unwrap());
+        drop(collector);
+
+        assert!(uring.submitter().push(Nop::new()).is_ok());
+        let stats = uring.overflow_stats();
+        assert_eq!(stats, OverflowStats { dropped: 0, flushes: 1, throttled: 1 });
+    }
+}
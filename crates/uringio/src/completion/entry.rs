@@ -1,6 +1,5 @@
 use std::{
     marker::PhantomData,
-    mem::transmute,
     ops::{Deref, DerefMut},
 };
 
@@ -110,10 +109,6 @@ impl CqeMix {
     pub fn is_cqe32(&self) -> bool {
         self.flags.contains(IoUringCqeFlags::CQE_32)
     }
-
-    pub const unsafe fn ext_data(&self) -> &[u64; 2] {
-        unsafe { transmute(&self.ext_data) }
-    }
 }
 
 impl<T> From<T> for CqeMix
@@ -139,6 +134,35 @@ impl DerefMut for CqeMix {
     }
 }
 
+/// ## Mix Cqe
+/// Completion yielded by a `CqeMix` queue
+#[derive(Debug)]
+pub enum MixCqe<'c> {
+    Cqe16(&'c Cqe16),
+    Cqe32(&'c Cqe32),
+}
+
+impl MixCqe<'_> {
+    #[inline]
+    pub const fn ext_data(&self) -> Option<&[u64; 2]> {
+        match self {
+            Self::Cqe16(_) => None,
+            Self::Cqe32(cqe) => Some(cqe.ext_data()),
+        }
+    }
+}
+
+impl Deref for MixCqe<'_> {
+    type Target = IoUringCqe;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Cqe16(cqe) => cqe,
+            Self::Cqe32(cqe) => cqe,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -12,6 +12,19 @@ use crate::{
     uring::mode::Mode,
 };
 
+/// ## Overflow Stats
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct OverflowStats {
+    /// CQEs dropped by kernel
+    pub dropped: u32,
+
+    /// Overflow list flushes issued by collectors and on submit
+    pub flushes: u64,
+
+    /// Pushes rejected while CQ overflowed
+    pub throttled: u64,
+}
+
 /// ## Completion Queue
 #[derive(Debug)]
 pub struct CompletionQueue<'fd, A, M, S, C> {
@@ -24,6 +37,8 @@ pub struct CompletionQueue<'fd, A, M, S, C> {
     pub k_overflow: &'fd AtomicU32,
 
     pub k_sq_flags: &'fd AtomicU32,
+    pub(crate) flushes: u64,
+    pub(crate) poisoned: bool,
 
     _marker_: PhantomData<(A, M, S)>,
 }
@@ -55,6 +70,8 @@ where
                 k_flags,
                 k_overflow,
                 k_sq_flags,
+                flushes: 0,
+                poisoned: false,
                 _marker_: PhantomData,
             }
         }
@@ -72,10 +89,37 @@ impl<A, M, S, C> CompletionQueue<'_, A, M, S, C> {
         IoUringSqFlags::from_bits_retain(bits)
     }
 
+    /// Only `IORING_CQ_EVENTFD_DISABLED` is writable by userspace
+    pub fn insert_flags(&self, flags: IoUringCqFlags, order: Ordering) {
+        self.k_flags.fetch_or(flags.bits(), order);
+    }
+
+    pub fn remove_flags(&self, flags: IoUringCqFlags, order: Ordering) {
+        self.k_flags.fetch_and(!flags.bits(), order);
+    }
+
+    /// CQEs dropped by kernel
     pub fn overflow(&self) -> u32 {
         self.k_overflow.load(Ordering::Acquire)
     }
 
+    /// Kernel holds CQEs in its overflow list, flushed by `GETEVENTS`
+    pub fn cq_overflow(&self) -> bool {
+        self.sq_flags(Ordering::Acquire).contains(IoUringSqFlags::CQ_OVERFLOW)
+    }
+
+    /// Overflow list flushes issued by collectors
+    #[inline]
+    pub const fn flushes(&self) -> u64 {
+        self.flushes
+    }
+
+    /// Rings switched by a resize whose remap failed, see `Uring::resize`
+    #[inline]
+    pub const fn is_poisoned(&self) -> bool {
+        self.poisoned
+    }
+
     #[inline]
     pub const fn get_cqe(&self, idx: u32) -> NonNull<C> {
         // SAFETY: index masked
@@ -114,7 +158,6 @@ impl<A, M, S, C> Index<u32> for CompletionQueue<'_, A, M, S, C> {
 
     #[inline]
     fn index(&self, index: u32) -> &Self::Output {
-        // TODO: handle SQARRAY SubmissionIndex
         unsafe { self.get_cqe(index).as_ref() }
     }
 }
@@ -0,0 +1,256 @@
+use std::mem;
+
+use crate::{
+    completion::result::Completion,
+    platform::iouring::{IoUringCqe, IoUringUserData},
+    shared::{error::Result, log::debug},
+};
+
+/// ## Token
+/// Registry slot index and generation, encoded into `user_data`
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Token {
+    index: u32,
+    generation: u32,
+}
+
+impl Token {
+    #[inline]
+    pub const fn index(&self) -> u32 {
+        self.index
+    }
+
+    #[inline]
+    pub const fn generation(&self) -> u32 {
+        self.generation
+    }
+
+    #[inline]
+    pub const fn from_u64(data: u64) -> Self {
+        #![allow(clippy::as_conversions, clippy::cast_possible_truncation)]
+        Self { index: data as u32, generation: (data >> 32) as u32 }
+    }
+
+    #[inline]
+    pub const fn as_u64(&self) -> u64 {
+        #![allow(clippy::as_conversions)]
+        ((self.generation as u64) << 32) | self.index as u64
+    }
+}
+
+impl From<Token> for IoUringUserData {
+    fn from(token: Token) -> Self {
+        Self::from_u64(token.as_u64())
+    }
+}
+
+/// ## Completed
+/// Request state after one of its CQEs
+#[derive(Debug)]
+pub enum Completed<'r, T> {
+    /// `IORING_CQE_F_MORE` set, e.g. multishot or zero-copy send, state kept for later CQEs
+    More(&'r mut T, Result<u32>),
+
+    /// Final CQE, slot released
+    Done(T, Result<u32>),
+}
+
+#[derive(Debug)]
+enum Slot<T> {
+    Occupied { generation: u32, state: T },
+    Vacant { generation: u32, next: u32 },
+}
+
+/// ## Registry
+/// Slab of per-request state keyed by [`Token`], state may borrow buffers for the request lifetime
+#[derive(Debug)]
+pub struct Registry<T> {
+    slots: Vec<Slot<T>>,
+    free: u32,
+    len: usize,
+}
+
+impl<T> Default for Registry<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Registry<T> {
+    const NONE: u32 = u32::MAX;
+
+    pub const fn new() -> Self {
+        Self { slots: Vec::new(), free: Self::NONE, len: 0 }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { slots: Vec::with_capacity(capacity), free: Self::NONE, len: 0 }
+    }
+
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Store `state`, the token goes into the SQE `user_data`
+    ///
+    /// # Panics
+    /// More than `u32::MAX - 1` slots
+    pub fn insert(&mut self, state: T) -> Token {
+        self.len += 1;
+
+        if let Some(slot) = self.slots.get_mut(self.free as usize) {
+            let Slot::Vacant { generation, next } = *slot else {
+                unreachable!("free list points to occupied slot");
+            };
+            let token = Token { index: self.free, generation };
+            *slot = Slot::Occupied { generation, state };
+            self.free = next;
+            return token;
+        }
+
+        let index = u32::try_from(self.slots.len()).expect("registry exceeds u32 tokens");
+        assert!(index != Self::NONE, "registry exceeds u32 tokens");
+        self.slots.push(Slot::Occupied { generation: 0, state });
+        Token { index, generation: 0 }
+    }
+
+    pub fn get(&self, token: Token) -> Option<&T> {
+        match self.slots.get(token.index as usize)? {
+            Slot::Occupied { generation, state } if *generation == token.generation => Some(state),
+            _ => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, token: Token) -> Option<&mut T> {
+        match self.slots.get_mut(token.index as usize)? {
+            Slot::Occupied { generation, state } if *generation == token.generation => Some(state),
+            _ => None,
+        }
+    }
+
+    /// Release the slot, later CQEs carrying `token` are stale
+    pub fn remove(&mut self, token: Token) -> Option<T> {
+        let slot = self.slots.get_mut(token.index as usize)?;
+        match slot {
+            Slot::Occupied { generation, .. } if *generation == token.generation => {},
+            _ => return None,
+        }
+
+        let vacant = Slot::Vacant { generation: token.generation.wrapping_add(1), next: self.free };
+        let Slot::Occupied { state, .. } = mem::replace(slot, vacant) else {
+            unreachable!("slot checked occupied");
+        };
+        self.free = token.index;
+        self.len -= 1;
+
+        Some(state)
+    }
+
+    /// State of the request completed by `cqe`, released on its final CQE.
+    /// `None` for stale or unknown tokens
+    pub fn complete(&mut self, cqe: &IoUringCqe) -> Option<Completed<'_, T>> {
+        let token = Token::from_u64(cqe.user_data.u64_());
+        if self.get(token).is_none() {
+            debug!("stale cqe: {token:?}");
+            return None;
+        }
+
+        if cqe.has_more() {
+            return self.get_mut(token).map(|state| Completed::More(state, cqe.result()));
+        }
+        self.remove(token).map(|state| Completed::Done(state, cqe.result()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        completion::entry::Cqe16,
+        operator::noop::Nop,
+        platform::iouring::{Errno, IoUringCqeFlags},
+        shared::macros::test_uring,
+        submission::{entry::Sqe64, submitter::Submit},
+        uring::mode::Iopoll,
+    };
+
+    #[test]
+    fn test_registry_generation() {
+        let mut registry = Registry::new();
+        let a = registry.insert("a");
+        assert_eq!(registry.remove(a), Some("a"));
+        assert_eq!(registry.remove(a), None);
+
+        // Slot reused with next generation
+        let b = registry.insert("b");
+        assert_eq!((b.index(), b.generation()), (a.index(), a.generation() + 1));
+        assert_eq!(registry.get(a), None);
+        assert_eq!(registry.get(b), Some(&"b"));
+        assert_eq!(Token::from_u64(b.as_u64()), b);
+        assert_eq!(registry.len(), 1);
+    }
+
+    #[test]
+    fn test_registry_complete() {
+        test_uring!(uring = Iopoll::new::<Sqe64, Cqe16>(8));
+
+        let mut bufs = [[0u8; 8]; 2];
+        let mut registry = Registry::new();
+        let (enter, mut submitter, mut collector) = uring.borrow();
+        for (buf, res) in bufs.iter_mut().zip([7, -Errno::AGAIN.raw_os_error()]) {
+            let token = registry.insert(buf);
+            submitter.push(Nop::new().inject_result(res).user_data(token)).unwrap();
+        }
+        assert_eq!(submitter.submit(enter, 2).unwrap(), 2);
+
+        collector.update_tail();
+        let cqe = collector.next().unwrap();
+        let Some(Completed::Done(buf, res)) = registry.complete(cqe) else {
+            panic!("expected final cqe");
+        };
+        buf[0] = 1;
+        assert_eq!(res.unwrap(), 7);
+        assert!(registry.complete(cqe).is_none());
+
+        let Some(Completed::Done(_, res)) = registry.complete(collector.next().unwrap()) else {
+            panic!("expected final cqe");
+        };
+        assert_eq!(res.unwrap_err().raw_os_error(), Some(Errno::AGAIN.raw_os_error()));
+        assert!(registry.is_empty());
+        drop(collector);
+
+        assert_eq!(bufs[0][0], 1);
+    }
+
+    #[test]
+    fn test_registry_complete_more() {
+        let mut registry = Registry::new();
+        let token = registry.insert(0);
+
+        let mut cqe = Cqe16::default();
+        cqe.user_data = token.into();
+        cqe.res = 16;
+        cqe.flags = IoUringCqeFlags::MORE;
+
+        // State kept while F_MORE is set
+        for shot in 1..3 {
+            let Some(Completed::More(count, res)) = registry.complete(&cqe) else {
+                panic!("expected more cqe");
+            };
+            *count += 1;
+            assert_eq!((*count, res.unwrap()), (shot, 16));
+        }
+
+        cqe.res = 0;
+        cqe.flags = IoUringCqeFlags::empty();
+        assert!(matches!(registry.complete(&cqe), Some(Completed::Done(2, Ok(0)))));
+        assert!(registry.complete(&cqe).is_none());
+        assert!(registry.is_empty());
+    }
+}
@@ -0,0 +1,137 @@
+use std::io::Error;
+
+use crate::{
+    completion::entry::{Cqe16, Cqe32, CqeMix, MixCqe},
+    platform::iouring::{IOURING_CQE_BUFFER_SHIFT, IoUringCqe, IoUringCqeFlags},
+    shared::error::Result,
+};
+
+/// ## Completion
+/// Decoded view of a CQE `res` and `flags`
+pub trait Completion {
+    fn raw(&self) -> &IoUringCqe;
+
+    /// Negative `res` mapped to its errno
+    #[inline]
+    fn result(&self) -> Result<u32> {
+        let res = self.raw().res;
+        if res < 0 {
+            return Err(Error::from_raw_os_error(-res));
+        }
+
+        Ok(res.cast_unsigned())
+    }
+
+    #[inline]
+    fn contains(&self, flags: IoUringCqeFlags) -> bool {
+        self.raw().flags.contains(flags)
+    }
+
+    /// Provided buffer picked by the kernel, `IORING_CQE_F_BUFFER`
+    #[inline]
+    fn buffer_id(&self) -> Option<u16> {
+        if !self.contains(IoUringCqeFlags::BUFFER) {
+            return None;
+        }
+
+        u16::try_from(self.raw().flags.bits() >> IOURING_CQE_BUFFER_SHIFT).ok()
+    }
+
+    /// Multishot request still armed, `IORING_CQE_F_MORE`
+    #[inline]
+    fn has_more(&self) -> bool {
+        self.contains(IoUringCqeFlags::MORE)
+    }
+
+    /// Socket has more data to read, `IORING_CQE_F_SOCK_NONEMPTY`
+    #[inline]
+    fn sock_nonempty(&self) -> bool {
+        self.contains(IoUringCqeFlags::SOCK_NONEMPTY)
+    }
+
+    /// Zero-copy send buffer released, `IORING_CQE_F_NOTIF`
+    #[inline]
+    fn is_notif(&self) -> bool {
+        self.contains(IoUringCqeFlags::NOTIF)
+    }
+
+    /// Incremental buffer partially consumed, `IORING_CQE_F_BUF_MORE`
+    #[inline]
+    fn buf_more(&self) -> bool {
+        self.contains(IoUringCqeFlags::BUF_MORE)
+    }
+
+    /// Padding entry posted by kernel at ring wrap, `IORING_CQE_F_SKIP`
+    #[inline]
+    fn is_skip(&self) -> bool {
+        self.contains(IoUringCqeFlags::SKIP)
+    }
+}
+
+impl Completion for IoUringCqe {
+    #[inline]
+    fn raw(&self) -> &IoUringCqe {
+        self
+    }
+}
+
+impl Completion for Cqe16 {
+    #[inline]
+    fn raw(&self) -> &IoUringCqe {
+        self
+    }
+}
+
+impl Completion for Cqe32 {
+    #[inline]
+    fn raw(&self) -> &IoUringCqe {
+        self
+    }
+}
+
+impl Completion for CqeMix {
+    #[inline]
+    fn raw(&self) -> &IoUringCqe {
+        self
+    }
+}
+
+impl Completion for MixCqe<'_> {
+    #[inline]
+    fn raw(&self) -> &IoUringCqe {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platform::iouring::Errno;
+
+    fn cqe(res: i32, flags: IoUringCqeFlags) -> Cqe16 {
+        let mut cqe = Cqe16::default();
+        cqe.res = res;
+        cqe.flags = flags;
+        cqe
+    }
+
+    #[test]
+    fn test_completion_result() {
+        assert_eq!(cqe(42, IoUringCqeFlags::empty()).result().unwrap(), 42);
+
+        let err = cqe(-Errno::AGAIN.raw_os_error(), IoUringCqeFlags::empty()).result().unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(Errno::AGAIN.raw_os_error()));
+    }
+
+    #[test]
+    fn test_completion_flags() {
+        let flags = IoUringCqeFlags::from_bits_retain(7 << IOURING_CQE_BUFFER_SHIFT);
+        let more = cqe(16, flags | IoUringCqeFlags::BUFFER | IoUringCqeFlags::MORE);
+        assert_eq!(more.buffer_id(), Some(7));
+        assert!(more.has_more());
+        assert!(!more.sock_nonempty() && !more.is_notif() && !more.buf_more() && !more.is_skip());
+
+        // Buffer id bits are meaningless without F_BUFFER
+        assert_eq!(cqe(0, flags).buffer_id(), None);
+    }
+}
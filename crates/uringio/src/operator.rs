@@ -1,10 +1,15 @@
+pub mod epoll;
 pub mod fd;
+pub mod fixed_fd;
 pub mod noop;
 pub mod opcode;
+pub mod pipe;
 pub mod read;
+pub mod read_multishot;
 
 use crate::{
-    platform::iouring::IoUringOp,
+    platform::iouring::{IoUringCqe, IoUringOp},
+    shared::error::Result,
     submission::entry::{FixSqe, Sqe, Sqe64, Sqe128},
 };
 
@@ -32,6 +37,14 @@ pub trait Op: Sized {
     }
 }
 
+/// ## Op Output
+/// Typed result of an operator, decoded from its CQE
+pub trait OpOutput: Op {
+    type Output;
+
+    fn output(cqe: &IoUringCqe) -> Result<Self::Output>;
+}
+
 impl<T> FixSqe for T where T: Op {}
 
 #[cfg(feature = "unstable-toolchain")]
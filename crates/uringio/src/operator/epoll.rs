@@ -0,0 +1,5 @@
+mod ctl;
+mod wait;
+
+pub use ctl::EpollCtl;
+pub use wait::{EpollEvents, EpollWait};
@@ -0,0 +1,165 @@
+use std::marker::PhantomData;
+
+use crate::{
+    completion::result::Completion,
+    operator::{Op, OpOutput},
+    platform::iouring::{
+        AsFd, AsRawFd, EpollCtlOp, EpollEvent, IoUringCqe, IoUringOp, IoUringPtr, IoUringSqeFlags,
+        IoUringUserData, RawFd,
+    },
+    register::personality::PersonalityId,
+    shared::{
+        error::Result,
+        macros::op,
+        null::{NULL, Null},
+    },
+    submission::entry::Sqe64,
+};
+
+/// ## Epoll Ctl
+/// Kernel resolves both fds with `fget`, `FixFd` is not supported
+#[derive(Debug)]
+#[op(EpollCtl, Entry = Sqe64)]
+#[repr(C)]
+pub struct EpollCtl<'fd, 'ev> {
+    pub opcode: IoUringOp,
+    pub flags: IoUringSqeFlags,
+    _unused0_: [u8; 2],
+    pub epfd: RawFd,
+    pub fd: RawFd,
+    _unused1_: [u8; 4],
+    pub event: IoUringPtr,
+    pub op: EpollCtlOp,
+    _unused2_: [u8; 4],
+    #[setter]
+    pub user_data: IoUringUserData,
+    _unused3_: [u8; 2],
+    #[setter]
+    pub personality: PersonalityId,
+    _unused4_: [u8; 4],
+    _unused5_: [u8; 16],
+
+    _marker_: PhantomData<(&'fd (), &'ev EpollEvent)>,
+}
+
+impl<'fd, 'ev> EpollCtl<'fd, 'ev> {
+    fn new<Ep, Fd>(epfd: &'fd Ep, fd: &'fd Fd, op: EpollCtlOp, event: IoUringPtr) -> Self
+    where
+        Ep: AsFd,
+        Fd: AsFd,
+    {
+        Self {
+            opcode: Self::OP_CODE,
+            flags: IoUringSqeFlags::empty(),
+            _unused0_: Default::default(),
+            epfd: epfd.as_fd().as_raw_fd(),
+            fd: fd.as_fd().as_raw_fd(),
+            _unused1_: Default::default(),
+            event,
+            op,
+            _unused2_: Default::default(),
+            user_data: Default::default(),
+            _unused3_: Default::default(),
+            personality: Default::default(),
+            _unused4_: Default::default(),
+            _unused5_: Default::default(),
+            _marker_: PhantomData,
+        }
+    }
+
+    /// `EPOLL_CTL_ADD`
+    pub fn add<Ep, Fd>(epfd: &'fd Ep, fd: &'fd Fd, event: &'ev EpollEvent) -> Self
+    where
+        Ep: AsFd,
+        Fd: AsFd,
+    {
+        let event = IoUringPtr::new((&raw const *event).cast_mut().cast());
+        Self::new(epfd, fd, EpollCtlOp::Add, event)
+    }
+
+    /// `EPOLL_CTL_MOD`
+    pub fn modify<Ep, Fd>(epfd: &'fd Ep, fd: &'fd Fd, event: &'ev EpollEvent) -> Self
+    where
+        Ep: AsFd,
+        Fd: AsFd,
+    {
+        let event = IoUringPtr::new((&raw const *event).cast_mut().cast());
+        Self::new(epfd, fd, EpollCtlOp::Mod, event)
+    }
+
+    /// `EPOLL_CTL_DEL`
+    pub fn delete<Ep, Fd>(epfd: &'fd Ep, fd: &'fd Fd) -> Self
+    where
+        Ep: AsFd,
+        Fd: AsFd,
+    {
+        Self::new(epfd, fd, EpollCtlOp::Del, IoUringPtr::null())
+    }
+}
+
+impl OpOutput for EpollCtl<'_, '_> {
+    type Output = Null;
+
+    fn output(cqe: &IoUringCqe) -> Result<Self::Output> {
+        cqe.result().map(|_| NULL)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rustix::{event::epoll, io::Errno};
+
+    use super::*;
+    use crate::{
+        completion::entry::Cqe16,
+        platform::iouring::{EpollEventData, EpollEventFlags},
+        shared::macros::test_uring,
+        submission::submitter::Submit,
+        uring::mode::{Iopoll, Sqpoll},
+    };
+
+    #[test]
+    fn test_epoll_ctl() {
+        test_uring!(uring = Sqpoll::new::<Sqe64, Cqe16>(8));
+
+        let epfd = epoll::create(epoll::CreateFlags::CLOEXEC).unwrap();
+        let (reader, _writer) = std::io::pipe().unwrap();
+        let event = EpollEvent { flags: EpollEventFlags::IN, data: EpollEventData::new_u64(7) };
+
+        let (enter, mut submitter, mut collector) = uring.borrow();
+        let ops = [
+            EpollCtl::add(&epfd, &reader, &event),
+            EpollCtl::modify(&epfd, &reader, &event),
+            EpollCtl::delete(&epfd, &reader),
+            EpollCtl::delete(&epfd, &reader),
+        ];
+        let results: Vec<_> = ops
+            .into_iter()
+            .map(|op| {
+                submitter.push(op).unwrap();
+                submitter.submit(enter).unwrap();
+                collector.update_head();
+                collector.flush(enter, 1).unwrap();
+                collector.update_tail();
+                collector.next().unwrap().res
+            })
+            .collect();
+        assert_eq!(results, [0, 0, 0, -Errno::NOENT.raw_os_error()]);
+    }
+
+    #[test]
+    fn test_epoll_ctl_iopoll() {
+        test_uring!(uring = Iopoll::new::<Sqe64, Cqe16>(8));
+
+        let epfd = epoll::create(epoll::CreateFlags::CLOEXEC).unwrap();
+        let (reader, _writer) = std::io::pipe().unwrap();
+
+        let (enter, mut submitter, mut collector) = uring.borrow();
+        submitter.push(EpollCtl::delete(&epfd, &reader)).unwrap();
+        assert_eq!(submitter.submit(enter, 1).unwrap(), 1);
+
+        collector.update_tail();
+        let cqe = collector.next().unwrap();
+        assert_eq!(cqe.res, -Errno::INVAL.raw_os_error());
+    }
+}
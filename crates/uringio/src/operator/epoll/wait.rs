@@ -0,0 +1,194 @@
+use std::{fmt, marker::PhantomData, ops::Deref};
+
+use crate::{
+    completion::result::Completion,
+    operator::{Op, OpOutput, fd::OpFd},
+    platform::iouring::{
+        EpollEvent, EpollEventData, EpollEventFlags, IoUringCqe, IoUringOp, IoUringPtr,
+        IoUringSqeFlags, IoUringUserData, RawFd,
+    },
+    register::personality::PersonalityId,
+    shared::{
+        error::{Result, err},
+        macros::op,
+    },
+    submission::entry::Sqe64,
+};
+
+/// ## Epoll Wait
+#[derive(Debug)]
+#[op(EpollWait, Entry = Sqe64)]
+#[repr(C)]
+pub struct EpollWait<'fd, 'dst> {
+    pub opcode: IoUringOp,
+    pub flags: IoUringSqeFlags,
+    _unused0_: [u8; 2],
+    pub epfd: RawFd,
+    _unused1_: u64,
+    pub events: IoUringPtr,
+    pub maxevents: u32,
+    _unused2_: [u8; 4],
+    #[setter]
+    pub user_data: IoUringUserData,
+    _unused3_: [u8; 2],
+    #[setter]
+    pub personality: PersonalityId,
+    _unused4_: [u8; 4],
+    _unused5_: [u8; 16],
+
+    _marker_: PhantomData<(&'fd (), &'dst mut EpollEvents)>,
+}
+
+impl<'fd, 'dst> EpollWait<'fd, 'dst> {
+    /// Waits into the spare capacity of `events`, see [`EpollEvents::set_len`]
+    pub fn new<Fd>(epfd: &'fd Fd, events: &'dst mut EpollEvents) -> Self
+    where
+        Fd: OpFd,
+    {
+        events.clear();
+
+        Self {
+            opcode: Self::OP_CODE,
+            flags: Fd::SQE_FLAG,
+            _unused0_: Default::default(),
+            epfd: epfd.raw_fd(),
+            _unused1_: 0,
+            events: IoUringPtr::new(events.buf.as_mut_ptr().cast()),
+            maxevents: events.maxevents,
+            _unused2_: Default::default(),
+            user_data: Default::default(),
+            _unused3_: Default::default(),
+            personality: Default::default(),
+            _unused4_: Default::default(),
+            _unused5_: Default::default(),
+            _marker_: PhantomData,
+        }
+    }
+}
+
+impl OpOutput for EpollWait<'_, '_> {
+    /// Number of ready events written
+    type Output = usize;
+
+    fn output(cqe: &IoUringCqe) -> Result<Self::Output> {
+        Ok(cqe.result()? as usize)
+    }
+}
+
+/// ## Epoll Events
+/// Owned `epoll_event` array filled by [`EpollWait`]
+pub struct EpollEvents {
+    buf: Vec<EpollEvent>,
+    maxevents: u32,
+}
+
+impl EpollEvents {
+    /// Kernel `EP_MAX_EVENTS`
+    #[allow(clippy::as_conversions, clippy::cast_possible_truncation)]
+    pub const MAX_EVENTS: u32 =
+        (i32::MAX.cast_unsigned() as usize / size_of::<EpollEvent>()) as u32;
+
+    pub fn new(capacity: usize) -> Result<Self> {
+        let maxevents = u32::try_from(capacity).ok().filter(|n| (1..=Self::MAX_EVENTS).contains(n));
+        let Some(maxevents) = maxevents else {
+            return err!("Epoll events capacity {capacity} out of range: 1..={}", Self::MAX_EVENTS);
+        };
+
+        Ok(Self { buf: Vec::with_capacity(capacity), maxevents })
+    }
+
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.buf.capacity()
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.buf.clear();
+    }
+
+    /// Unsafe: `len` must be the `res` of the completed [`EpollWait`]
+    #[inline]
+    pub unsafe fn set_len(&mut self, len: usize) {
+        debug_assert!(len <= self.capacity());
+
+        // SAFETY: first `len` events written by kernel
+        unsafe { self.buf.set_len(len) }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (EpollEventFlags, EpollEventData)> {
+        self.buf.iter().map(|ev| (ev.flags, ev.data))
+    }
+}
+
+impl Deref for EpollEvents {
+    type Target = [EpollEvent];
+
+    fn deref(&self) -> &Self::Target {
+        &self.buf
+    }
+}
+
+impl fmt::Debug for EpollEvents {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EpollEvents")
+            .field("len", &self.buf.len())
+            .field("maxevents", &self.maxevents)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use rustix::event::epoll;
+
+    use super::*;
+    use crate::{
+        completion::entry::Cqe16, operator::epoll::EpollCtl, shared::macros::test_uring,
+        submission::submitter::Submit, uring::mode::Sqpoll,
+    };
+
+    #[test]
+    fn test_epoll_events_capacity() {
+        assert!(EpollEvents::new(0).is_err());
+        assert!(EpollEvents::new(EpollEvents::MAX_EVENTS as usize + 1).is_err());
+        assert_eq!(EpollEvents::new(4).unwrap().maxevents, 4);
+    }
+
+    #[test]
+    fn test_epoll_wait() {
+        test_uring!(uring = Sqpoll::new::<Sqe64, Cqe16>(8));
+
+        let epfd = epoll::create(epoll::CreateFlags::CLOEXEC).unwrap();
+        let (reader, mut writer) = std::io::pipe().unwrap();
+        let event = EpollEvent { flags: EpollEventFlags::IN, data: EpollEventData::new_u64(7) };
+        let mut events = EpollEvents::new(4).unwrap();
+
+        let (enter, mut submitter, mut collector) = uring.borrow();
+        submitter.push(EpollCtl::add(&epfd, &reader, &event).user_data(1)).unwrap();
+        submitter.push(EpollWait::new(&epfd, &mut events).user_data(2)).unwrap();
+        submitter.submit(enter).unwrap();
+
+        collector.update_head();
+        collector.flush(enter, 1).unwrap();
+        collector.update_tail();
+        let cqe = collector.next().unwrap();
+        assert_eq!((cqe.user_data.u64_(), cqe.res), (1, 0));
+
+        // Pending until the pipe turns readable
+        assert!(collector.next().is_none());
+        writer.write_all(b"epoll").unwrap();
+
+        collector.update_head();
+        collector.flush(enter, 1).unwrap();
+        collector.update_tail();
+        let cqe = collector.next().unwrap();
+        assert_eq!((cqe.user_data.u64_(), cqe.res), (2, 1));
+
+        unsafe { events.set_len(1) };
+        let ready: Vec<_> = events.iter().map(|(flags, data)| (flags, data.u64())).collect();
+        assert_eq!(ready, [(EpollEventFlags::IN, 7)]);
+    }
+}
@@ -0,0 +1,130 @@
+use std::{marker::PhantomData, os::fd::FromRawFd};
+
+use crate::{
+    completion::result::Completion,
+    operator::{
+        Op, OpOutput,
+        fd::{FixFd, OpFd},
+    },
+    platform::iouring::{
+        IoUringCqe, IoUringFixedFdFlags, IoUringOp, IoUringSqeFlags, IoUringUserData, OwnedFd,
+        RawFd,
+    },
+    shared::{error::Result, macros::op},
+    submission::entry::Sqe64,
+};
+
+/// ## Fixed Fd Install
+/// Installs a direct descriptor into the process fd table, `O_CLOEXEC` by default
+#[derive(Debug)]
+#[op(FixedFdInstall, Entry = Sqe64)]
+#[repr(C)]
+pub struct FixedFdInstall<'fd> {
+    pub opcode: IoUringOp,
+    pub flags: IoUringSqeFlags,
+    _unused0_: [u8; 2],
+    pub fd: RawFd,
+    _unused1_: u64,
+    _unused2_: u64,
+    _unused3_: u32,
+    pub install_fd_flags: IoUringFixedFdFlags,
+    #[setter]
+    pub user_data: IoUringUserData,
+    _unused4_: [u8; 4], // NOTE: personality is rejected with EPERM
+    _unused5_: [u8; 4],
+    _unused6_: [u8; 16],
+
+    _marker_: PhantomData<&'fd FixFd>,
+}
+
+impl<'fd> FixedFdInstall<'fd> {
+    pub fn new(fd: &'fd FixFd) -> Self {
+        Self {
+            opcode: Self::OP_CODE,
+            flags: IoUringSqeFlags::FIXED_FILE,
+            _unused0_: Default::default(),
+            fd: fd.raw_fd(),
+            _unused1_: 0,
+            _unused2_: 0,
+            _unused3_: 0,
+            install_fd_flags: IoUringFixedFdFlags::empty(),
+            user_data: Default::default(),
+            _unused4_: Default::default(),
+            _unused5_: Default::default(),
+            _unused6_: Default::default(),
+            _marker_: PhantomData,
+        }
+    }
+
+    pub fn no_cloexec(mut self) -> Self {
+        self.install_fd_flags |= IoUringFixedFdFlags::NO_CLOEXEC;
+        self
+    }
+
+    /// Unsafe: `cqe` must be the completion of a [`FixedFdInstall`]
+    pub unsafe fn installed_fd(cqe: &IoUringCqe) -> Result<OwnedFd> {
+        let fd = Self::output(cqe)?;
+
+        // SAFETY: kernel installed a new fd owned by the caller
+        Ok(unsafe { OwnedFd::from_raw_fd(fd) })
+    }
+}
+
+impl OpOutput for FixedFdInstall<'_> {
+    /// Installed fd, take ownership with [`FixedFdInstall::installed_fd`]
+    type Output = RawFd;
+
+    fn output(cqe: &IoUringCqe) -> Result<Self::Output> {
+        Ok(cqe.result()?.cast_signed())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs::File,
+        io::{Read, Write},
+        time::Duration,
+    };
+
+    use super::*;
+    use crate::{
+        completion::entry::Cqe16,
+        platform::iouring::{AsRawFd, IoUringRegisterOp},
+        shared::macros::test_uring,
+        submission::submitter::Submit,
+        uring::mode::Sqpoll,
+    };
+
+    #[test]
+    fn test_fixed_fd_install() {
+        test_uring!(uring = Sqpoll::new::<Sqe64, Cqe16>(8));
+
+        let (reader, mut writer) = std::io::pipe().unwrap();
+        let files = [reader.as_raw_fd(), -1];
+        let ptr = files.as_ptr().cast();
+        unsafe { uring.enter.register(IoUringRegisterOp::RegisterFiles, ptr, 2).unwrap() };
+        drop(reader);
+
+        let (enter, mut submitter, mut collector) = uring.borrow();
+        let (fix_fd, empty) = unsafe { (FixFd::new(0), FixFd::new(1)) };
+        submitter.push(FixedFdInstall::new(&fix_fd).user_data(1)).unwrap();
+        submitter.push(FixedFdInstall::new(&empty).user_data(2)).unwrap();
+        submitter.submit(enter).unwrap();
+
+        collector.wait(enter, 2, Duration::from_secs(1)).unwrap();
+        let cqe = collector.next().unwrap();
+        assert!(FixedFdInstall::output(cqe).unwrap() >= 0);
+        let mut installed = File::from(unsafe { FixedFdInstall::installed_fd(cqe).unwrap() });
+
+        let cqe = collector.next().unwrap();
+        assert_eq!(cqe.user_data.u64_(), 2);
+        assert!(FixedFdInstall::output(cqe).is_err());
+
+        // Installed fd shares the registered pipe end
+        writer.write_all(b"fixed").unwrap();
+        let mut buf = [0; 5];
+        installed.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, b"fixed");
+    }
+}
@@ -1,9 +1,13 @@
 use std::marker::PhantomData;
 
 use crate::{
-    operator::{Op, fd::OpFd},
-    platform::iouring::{IoUringOp, IoUringSqeFlags, IoUringUserData, NopFlags, RawFd},
-    shared::macros::op,
+    completion::result::Completion,
+    operator::{Op, OpOutput, fd::OpFd},
+    platform::iouring::{
+        Errno, IoUringCqe, IoUringOp, IoUringSqeFlags, IoUringUserData, NopFlags, RawFd,
+    },
+    register::personality::PersonalityId,
+    shared::{error::Result, macros::op},
     submission::entry::Sqe64,
 };
 
@@ -15,16 +19,17 @@ pub struct Nop<'fd> {
     pub flags: IoUringSqeFlags,
     _unused0_: [u8; 2],
     pub fd: RawFd,
-    _unused1_: u64,
-    _unused2_: u64,
+    pub ext_data1: u64,
+    pub ext_data2: u64,
     pub len: u32,
     pub nop_flags: u32, // TODO: NopFlags
     #[setter]
     pub user_data: IoUringUserData,
     pub buf_index: u16,
-    _unused3_: [u8; 2],
-    _unused4_: [u8; 4],
-    _unused5_: [u8; 16],
+    #[setter]
+    pub personality: PersonalityId,
+    _unused1_: [u8; 4],
+    _unused2_: [u8; 16],
 
     _marker_: PhantomData<&'fd RawFd>,
 }
@@ -36,15 +41,15 @@ impl<'fd> Nop<'fd> {
             flags: IoUringSqeFlags::default(),
             _unused0_: Default::default(),
             fd: -1,
-            _unused1_: 0,
-            _unused2_: 0,
+            ext_data1: 0,
+            ext_data2: 0,
             len: 0,
             nop_flags: NopFlags::NONE,
             user_data: Default::default(),
             buf_index: Default::default(),
-            _unused3_: Default::default(),
-            _unused4_: Default::default(),
-            _unused5_: Default::default(),
+            personality: Default::default(),
+            _unused1_: Default::default(),
+            _unused2_: Default::default(),
             _marker_: PhantomData,
         }
     }
@@ -64,8 +69,22 @@ impl<'fd> Nop<'fd> {
         self
     }
 
-    pub fn skip_cqe(mut self) -> Self {
-        self.flags |= IoUringSqeFlags::CQE_SKIP_SUCCESS;
+    /// Complete with `res` instead of 0, kernel marks negative `res` as failed
+    pub fn inject_result(mut self, res: i32) -> Self {
+        self.len = res.cast_unsigned();
+        self.nop_flags |= NopFlags::INJECT_RESULT;
+        self
+    }
+
+    pub fn inject_errno(self, errno: Errno) -> Self {
+        self.inject_result(-errno.raw_os_error())
+    }
+
+    /// Requires `Cqe32` or `CqeMix` queue
+    pub fn set_ext_data(mut self, ext_data: [u64; 2]) -> Self {
+        self.ext_data1 = ext_data[0];
+        self.ext_data2 = ext_data[1];
+        self.nop_flags |= NopFlags::CQE32;
         self
     }
 
@@ -74,3 +93,57 @@ impl<'fd> Nop<'fd> {
         self
     }
 }
+
+impl OpOutput for Nop<'_> {
+    /// Injected result
+    type Output = u32;
+
+    fn output(cqe: &IoUringCqe) -> Result<Self::Output> {
+        cqe.result()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        completion::entry::{Cqe16, Cqe32},
+        shared::macros::test_uring,
+        submission::{entry::Sqe128, submitter::Submit},
+        uring::mode::Iopoll,
+    };
+
+    #[test]
+    fn test_inject_result() {
+        test_uring!(uring = Iopoll::new::<Sqe64, Cqe16>(8));
+
+        let injects =
+            [(1, -Errno::AGAIN.raw_os_error()), (2, -Errno::CANCELED.raw_os_error()), (3, 7)];
+
+        let (enter, mut submitter, mut collector) = uring.borrow();
+        for (user_data, res) in injects {
+            submitter.push(Nop::new().inject_result(res).user_data(user_data)).unwrap();
+        }
+        assert_eq!(submitter.submit(enter, 3).unwrap(), 3);
+
+        collector.update_tail();
+        let results: Vec<_> = collector.map(|cqe| (cqe.user_data.u64_(), cqe.res)).collect();
+        assert_eq!(results, injects);
+    }
+
+    #[test]
+    fn test_inject_ext_data() {
+        test_uring!(uring = Iopoll::new::<Sqe128, Cqe32>(8));
+
+        let (enter, mut submitter, mut collector) = uring.borrow();
+        let nop = Nop::new().inject_errno(Errno::AGAIN).set_ext_data([1, 2]);
+        submitter.push(Sqe64::from(nop.user_data(1))).unwrap();
+        assert_eq!(submitter.submit(enter, 1).unwrap(), 1);
+
+        collector.update_tail();
+        let cqe = collector.next().unwrap();
+        assert_eq!(cqe.result().unwrap_err().raw_os_error(), Some(Errno::AGAIN.raw_os_error()));
+        assert!(Nop::output(cqe).is_err());
+        assert_eq!(cqe.ext_data(), &[1, 2]);
+    }
+}
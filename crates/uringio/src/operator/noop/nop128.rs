@@ -1,9 +1,13 @@
 use std::marker::PhantomData;
 
 use crate::{
-    operator::{Op, fd::OpFd},
-    platform::iouring::{IoUringOp, IoUringSqeFlags, IoUringUserData, NopFlags, RawFd},
-    shared::macros::op,
+    completion::result::Completion,
+    operator::{Op, OpOutput, fd::OpFd},
+    platform::iouring::{
+        Errno, IoUringCqe, IoUringOp, IoUringSqeFlags, IoUringUserData, NopFlags, RawFd,
+    },
+    register::personality::PersonalityId,
+    shared::{error::Result, macros::op},
     submission::entry::Sqe64,
 };
 
@@ -22,9 +26,10 @@ pub struct Nop128<'fd> {
     #[setter]
     pub user_data: IoUringUserData,
     pub buf_index: u16,
-    _unused1_: [u8; 2],
-    _unused2_: [u8; 4],
-    _unused3_: [u8; 16],
+    #[setter]
+    pub personality: PersonalityId,
+    _unused1_: [u8; 4],
+    _unused2_: [u8; 16],
 
     _marker_: PhantomData<&'fd RawFd>,
 }
@@ -42,9 +47,9 @@ impl<'fd> Nop128<'fd> {
             nop_flags: NopFlags::NONE,
             user_data: Default::default(),
             buf_index: Default::default(),
+            personality: Default::default(),
             _unused1_: Default::default(),
             _unused2_: Default::default(),
-            _unused3_: Default::default(),
             _marker_: PhantomData,
         }
     }
@@ -64,6 +69,17 @@ impl<'fd> Nop128<'fd> {
         self
     }
 
+    /// Complete with `res` instead of 0, kernel marks negative `res` as failed
+    pub fn inject_result(mut self, res: i32) -> Self {
+        self.len = res.cast_unsigned();
+        self.nop_flags |= NopFlags::INJECT_RESULT;
+        self
+    }
+
+    pub fn inject_errno(self, errno: Errno) -> Self {
+        self.inject_result(-errno.raw_os_error())
+    }
+
     pub fn set_ext_data(mut self, ext_data: [u64; 2]) -> Self {
         self.ext_data1 = ext_data[0];
         self.ext_data2 = ext_data[1];
@@ -71,13 +87,17 @@ impl<'fd> Nop128<'fd> {
         self
     }
 
-    pub fn skip_cqe(mut self) -> Self {
-        self.flags |= IoUringSqeFlags::CQE_SKIP_SUCCESS;
-        self
-    }
-
     pub fn enable_tw(mut self) -> Self {
         self.nop_flags |= NopFlags::TW;
         self
     }
 }
+
+impl OpOutput for Nop128<'_> {
+    /// Injected result
+    type Output = u32;
+
+    fn output(cqe: &IoUringCqe) -> Result<Self::Output> {
+        cqe.result()
+    }
+}
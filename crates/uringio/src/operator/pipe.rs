@@ -0,0 +1,193 @@
+use std::{marker::PhantomData, os::fd::FromRawFd};
+
+use crate::{
+    completion::result::Completion,
+    operator::{Op, OpOutput, fd::FixFd},
+    platform::iouring::{
+        IOURING_FILE_INDEX_ALLOC, IoUringCqe, IoUringOp, IoUringPtr, IoUringSqeFlags,
+        IoUringUserData, OFlags, OwnedFd, RawFd,
+    },
+    register::personality::PersonalityId,
+    shared::{
+        error::Result,
+        macros::op,
+        null::{NULL, Null},
+    },
+    submission::entry::Sqe64,
+};
+
+/// ## Pipe
+/// Writes `[read, write]` fds, or direct descriptor slots with [`Pipe::direct`]
+#[derive(Debug)]
+#[op(Pipe, Entry = Sqe64)]
+#[repr(C)]
+pub struct Pipe<'dst> {
+    pub opcode: IoUringOp,
+    pub flags: IoUringSqeFlags,
+    _unused0_: [u8; 2],
+    _unused1_: RawFd, // NOTE: must be 0
+    _unused2_: u64,
+    pub fds: IoUringPtr,
+    _unused3_: u32,
+    pub pipe_flags: OFlags,
+    #[setter]
+    pub user_data: IoUringUserData,
+    _unused4_: [u8; 2],
+    #[setter]
+    pub personality: PersonalityId,
+    pub file_index: u32,
+    _unused5_: [u8; 16],
+
+    _marker_: PhantomData<&'dst mut [RawFd; 2]>,
+}
+
+impl<'dst> Pipe<'dst> {
+    pub fn new(fds: &'dst mut [RawFd; 2]) -> Self {
+        Self {
+            opcode: Self::OP_CODE,
+            flags: IoUringSqeFlags::empty(),
+            _unused0_: Default::default(),
+            _unused1_: 0,
+            _unused2_: 0,
+            fds: IoUringPtr::new(fds.as_mut_ptr().cast()),
+            _unused3_: 0,
+            pipe_flags: OFlags::CLOEXEC,
+            user_data: Default::default(),
+            _unused4_: Default::default(),
+            personality: Default::default(),
+            file_index: 0,
+            _unused5_: Default::default(),
+            _marker_: PhantomData,
+        }
+    }
+
+    /// `O_CLOEXEC` by default, also accepts `O_NONBLOCK` and `O_DIRECT`.
+    /// `O_CLOEXEC` is dropped for direct descriptors
+    pub fn set_pipe_flags(mut self, pipe_flags: OFlags) -> Self {
+        self.pipe_flags = pipe_flags;
+        if self.file_index != 0 {
+            self.pipe_flags.remove(OFlags::CLOEXEC);
+        }
+        self
+    }
+
+    /// Allocate two free slots in the registered file table
+    pub fn direct(mut self) -> Self {
+        // NOTE: O_CLOEXEC is rejected for direct descriptors
+        self.pipe_flags.remove(OFlags::CLOEXEC);
+        self.file_index = IOURING_FILE_INDEX_ALLOC.cast_unsigned();
+        self
+    }
+
+    /// Install into slots `slot` and `slot + 1` of the registered file table
+    pub fn direct_at(mut self, slot: u32) -> Self {
+        debug_assert!(slot < IOURING_FILE_INDEX_ALLOC.cast_unsigned() - 2);
+
+        self.pipe_flags.remove(OFlags::CLOEXEC);
+        self.file_index = slot + 1;
+        self
+    }
+
+    /// Unsafe: `cqe` and `fds` must come from a completed normal [`Pipe`]
+    pub unsafe fn into_fds(cqe: &IoUringCqe, fds: [RawFd; 2]) -> Result<(OwnedFd, OwnedFd)> {
+        Self::output(cqe)?;
+
+        // SAFETY: kernel installed two new fds owned by the caller
+        Ok(unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) })
+    }
+
+    /// Unsafe: `cqe` and `fds` must come from a completed direct [`Pipe`]
+    pub unsafe fn into_fix_fds(cqe: &IoUringCqe, fds: [RawFd; 2]) -> Result<(FixFd, FixFd)> {
+        Self::output(cqe)?;
+
+        // SAFETY: kernel wrote the two allocated slots
+        Ok(unsafe {
+            (FixFd::new(fds[0].cast_unsigned() as _), FixFd::new(fds[1].cast_unsigned() as _))
+        })
+    }
+}
+
+impl OpOutput for Pipe<'_> {
+    /// Fds are written to the destination array, see [`Pipe::into_fds`]
+    type Output = Null;
+
+    fn output(cqe: &IoUringCqe) -> Result<Self::Output> {
+        cqe.result().map(|_| NULL)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs::File,
+        io::{Read, Write},
+        time::Duration,
+    };
+
+    use super::*;
+    use crate::{
+        completion::entry::Cqe16,
+        operator::{fd::OpFd, fixed_fd::FixedFdInstall},
+        platform::iouring::IoUringRegisterOp,
+        shared::macros::test_uring,
+        submission::submitter::Submit,
+        uring::mode::Sqpoll,
+    };
+
+    #[test]
+    fn test_pipe() {
+        test_uring!(uring = Sqpoll::new::<Sqe64, Cqe16>(8));
+
+        let mut fds = [-1; 2];
+        let (enter, mut submitter, mut collector) = uring.borrow();
+        submitter.push(Pipe::new(&mut fds).user_data(1)).unwrap();
+        submitter.submit(enter).unwrap();
+
+        collector.wait(enter, 1, Duration::from_secs(1)).unwrap();
+        let cqe = collector.next().unwrap();
+        assert!(Pipe::output(cqe).is_ok());
+
+        let (rd, wr) = unsafe { Pipe::into_fds(cqe, fds).unwrap() };
+        File::from(wr).write_all(b"pipe").unwrap();
+        let mut buf = String::new();
+        File::from(rd).read_to_string(&mut buf).unwrap();
+        assert_eq!(buf, "pipe");
+    }
+
+    #[test]
+    fn test_pipe_direct() {
+        test_uring!(uring = Sqpoll::new::<Sqe64, Cqe16>(8));
+
+        // Sparse file table
+        let files = [-1; 4];
+        let ptr = files.as_ptr().cast();
+        unsafe { uring.enter.register(IoUringRegisterOp::RegisterFiles, ptr, 4).unwrap() };
+
+        let mut fds = [-1; 2];
+        let (enter, mut submitter, mut collector) = uring.borrow();
+        let flags = OFlags::CLOEXEC | OFlags::NONBLOCK;
+        let pipe = Pipe::new(&mut fds).direct_at(2).set_pipe_flags(flags);
+        assert_eq!(pipe.pipe_flags, OFlags::NONBLOCK);
+        submitter.push(pipe.user_data(1)).unwrap();
+        submitter.submit(enter).unwrap();
+
+        collector.wait(enter, 1, Duration::from_secs(1)).unwrap();
+        let cqe = collector.next().unwrap();
+        let (rd, wr) = unsafe { Pipe::into_fix_fds(cqe, fds).unwrap() };
+        assert_eq!((rd.raw_fd(), wr.raw_fd()), (2, 3));
+
+        // Direct descriptors are only reachable through the ring
+        submitter.push(FixedFdInstall::new(&rd).user_data(2)).unwrap();
+        submitter.push(FixedFdInstall::new(&wr).user_data(3)).unwrap();
+        submitter.submit(enter).unwrap();
+
+        collector.wait(enter, 2, Duration::from_secs(1)).unwrap();
+        let mut installed: Vec<_> = collector
+            .map(|cqe| File::from(unsafe { FixedFdInstall::installed_fd(cqe).unwrap() }))
+            .collect();
+        installed[1].write_all(b"direct").unwrap();
+        let mut buf = [0; 6];
+        installed[0].read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, b"direct");
+    }
+}
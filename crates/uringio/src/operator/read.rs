@@ -1,12 +1,14 @@
 use std::marker::PhantomData;
 
 use crate::{
-    operator::{Op, fd::OpFd},
+    completion::result::Completion,
+    operator::{Op, OpOutput, fd::OpFd},
     platform::iouring::{
-        IoUringOp, IoUringPiAttr, IoUringPtr, IoUringSqeFlags, IoUringUserData, RawFd,
+        IoUringCqe, IoUringOp, IoUringPiAttr, IoUringPtr, IoUringSqeFlags, IoUringUserData, RawFd,
         ReadWriteFlags,
     },
-    shared::macros::op,
+    register::personality::PersonalityId,
+    shared::{error::Result, macros::op},
     submission::entry::Sqe64,
 };
 
@@ -25,7 +27,8 @@ pub struct Read<'fd, 'dst> {
     #[setter]
     pub user_data: IoUringUserData,
     _unused0_: [u8; 2],
-    pub personality: u16,
+    #[setter]
+    pub personality: PersonalityId,
     _unused1_: [u8; 4],
     pub pi_attr: IoUringPiAttr,
 
@@ -55,3 +58,12 @@ impl<'fd, 'dst> Read<'fd, 'dst> {
         }
     }
 }
+
+impl OpOutput for Read<'_, '_> {
+    /// Bytes read, 0 at end of file
+    type Output = usize;
+
+    fn output(cqe: &IoUringCqe) -> Result<Self::Output> {
+        Ok(cqe.result()? as usize)
+    }
+}
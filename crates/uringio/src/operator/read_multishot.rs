@@ -0,0 +1,161 @@
+use std::marker::PhantomData;
+
+use crate::{
+    completion::result::Completion,
+    operator::{Op, OpOutput, fd::OpFd},
+    platform::iouring::{
+        IoUringCqe, IoUringOp, IoUringSqeFlags, IoUringUserData, RawFd, ReadWriteFlags,
+    },
+    register::personality::PersonalityId,
+    shared::{error::Result, macros::op},
+    submission::entry::Sqe64,
+};
+
+/// ## Read Multishot
+/// Requires buffer selection, each CQE carries the selected buffer id
+#[derive(Debug)]
+#[op(ReadMultishot, Entry = Sqe64)]
+#[repr(C)]
+pub struct ReadMultishot<'fd> {
+    pub opcode: IoUringOp,
+    pub flags: IoUringSqeFlags,
+    pub ioprio: u16,
+    pub fd: RawFd,
+    pub offset: u64,
+    _unused0_: u64,
+    pub len: u32,
+    pub rw_flags: ReadWriteFlags,
+    #[setter]
+    pub user_data: IoUringUserData,
+    pub buf_group: u16,
+    #[setter]
+    pub personality: PersonalityId,
+    _unused1_: [u8; 4],
+    _unused2_: [u8; 16],
+
+    _marker_: PhantomData<&'fd ()>,
+}
+
+impl<'fd> ReadMultishot<'fd> {
+    pub fn new<Fd>(fd: &'fd Fd, buf_group: u16) -> Self
+    where
+        Fd: OpFd,
+    {
+        Self {
+            opcode: Self::OP_CODE,
+            flags: Fd::SQE_FLAG | IoUringSqeFlags::BUFFER_SELECT,
+            ioprio: 0,
+            fd: fd.raw_fd(),
+            offset: 0,
+            _unused0_: 0,
+            len: 0,
+            rw_flags: Default::default(),
+            user_data: Default::default(),
+            buf_group,
+            personality: Default::default(),
+            _unused1_: Default::default(),
+            _unused2_: Default::default(),
+            _marker_: PhantomData,
+        }
+    }
+
+    /// Limit bytes per completion, 0 uses the whole selected buffer
+    pub fn set_len(mut self, len: u32) -> Self {
+        self.len = len;
+        self
+    }
+}
+
+impl OpOutput for ReadMultishot<'_> {
+    /// Bytes read into the buffer of [`Completion::buffer_id`]
+    type Output = usize;
+
+    fn output(cqe: &IoUringCqe) -> Result<Self::Output> {
+        Ok(cqe.result()? as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::Write,
+        ptr::null_mut,
+        sync::atomic::{AtomicU16, Ordering},
+        time::Duration,
+    };
+
+    use rustix::io_uring::{io_uring_buf, io_uring_buf_reg};
+
+    use super::*;
+    use crate::{
+        completion::{
+            entry::Cqe16,
+            registry::{Completed, Registry},
+        },
+        platform::{
+            iouring::{IoUringPtr, IoUringRegisterOp},
+            mmap::{MapFlags, Mmap, ProtFlags, page_size},
+        },
+        shared::macros::test_uring,
+        submission::submitter::Submit,
+        uring::mode::Sqpoll,
+    };
+
+    const BGID: u16 = 3;
+
+    #[test]
+    fn test_read_multishot() {
+        test_uring!(uring = Sqpoll::new::<Sqe64, Cqe16>(8));
+
+        // Provided buffer ring, tail overlays `resv` of the first entry
+        let mut bufs = [[0u8; 16]; 4];
+        let prot = ProtFlags::READ | ProtFlags::WRITE;
+        let ring =
+            unsafe { Mmap::mmap_anonymous(null_mut(), page_size(), prot, MapFlags::PRIVATE) }
+                .unwrap();
+        for (bid, buf) in (0..).zip(&mut bufs) {
+            let mut entry = io_uring_buf::default();
+            entry.addr = IoUringPtr::new(buf.as_mut_ptr().cast());
+            entry.len = 16;
+            entry.bid = bid;
+            unsafe { ring.ptr().cast::<io_uring_buf>().add(bid.into()).write(entry) };
+        }
+        let tail = unsafe { ring.ptr().byte_add(14).cast::<AtomicU16>().as_ref() };
+        tail.store(4, Ordering::Release);
+
+        let mut reg = io_uring_buf_reg::default();
+        reg.ring_addr = IoUringPtr::new(ring.ptr().as_ptr());
+        reg.ring_entries = 4;
+        reg.bgid = BGID;
+        let ptr = (&raw const reg).cast();
+        unsafe { uring.enter.register(IoUringRegisterOp::RegisterPbufRing, ptr, 1).unwrap() };
+
+        let (reader, mut writer) = std::io::pipe().unwrap();
+        let (enter, mut submitter, mut collector) = uring.borrow();
+        let mut registry = Registry::new();
+        let token = registry.insert(0);
+        submitter.push(ReadMultishot::new(&reader, BGID).user_data(token)).unwrap();
+        submitter.submit(enter).unwrap();
+
+        for (bid, data) in [b"hello", b"world"].into_iter().enumerate() {
+            writer.write_all(data).unwrap();
+            collector.wait(enter, 1, Duration::from_secs(1)).unwrap();
+
+            let cqe = collector.next().unwrap();
+            let len = ReadMultishot::output(cqe).unwrap();
+            let Some(Completed::More(reads, Ok(5))) = registry.complete(cqe) else {
+                panic!("expected armed request");
+            };
+            *reads += 1;
+            assert_eq!(cqe.buffer_id().map(usize::from), Some(bid));
+            assert_eq!(&bufs[bid][..len], data);
+        }
+
+        drop(writer);
+        collector.wait(enter, 1, Duration::from_secs(1)).unwrap();
+        let cqe = collector.next().unwrap();
+        // End of file terminates the request
+        assert!(matches!(registry.complete(cqe), Some(Completed::Done(2, Ok(0)))));
+        assert!(registry.is_empty());
+    }
+}
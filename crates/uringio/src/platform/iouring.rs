@@ -1,18 +1,29 @@
 pub use rustix::{
     fd::{AsFd, AsRawFd, BorrowedFd, OwnedFd, RawFd},
     ffi::c_void,
-    io::{ReadWriteFlags, Result},
+    io::{Errno, ReadWriteFlags, Result},
     io_uring::{
+        ClockId, EpollEvent, EpollEventData, EpollEventFlags,
+        IORING_CQE_BUFFER_SHIFT as IOURING_CQE_BUFFER_SHIFT,
+        IORING_FILE_INDEX_ALLOC as IOURING_FILE_INDEX_ALLOC,
         IORING_OFF_CQ_RING as IOURING_OFF_CQ_RING, IORING_OFF_SQ_RING as IOURING_OFF_SQ_RING,
         IORING_OFF_SQES as IOURING_OFF_SQES, IoringCqFlags as IoUringCqFlags,
         IoringCqeFlags as IoUringCqeFlags, IoringEnterFlags as IoUringEnterFlags,
-        IoringFeatureFlags as IoUringFeatureFlags, IoringOp as IoUringOp,
-        IoringRegisterOp as IoUringRegisterOp, IoringSetupFlags as IoUringSetupFlags,
-        IoringSqFlags as IoUringSqFlags, IoringSqeFlags as IoUringSqeFlags,
-        io_uring_cqe as IoUringCqe, io_uring_enter, io_uring_params as IoUringParams,
-        io_uring_ptr as IoUringPtr, io_uring_register, io_uring_rsrc_update as IoUringRsrcUpdate,
+        IoringFeatureFlags as IoUringFeatureFlags, IoringFixedFdFlags as IoUringFixedFdFlags,
+        IoringOp as IoUringOp, IoringOpFlags as IoUringOpFlags,
+        IoringRegisterFlags as IoUringRegisterFlags, IoringRegisterOp as IoUringRegisterOp,
+        IoringRestrictionOp as IoUringRestrictionOp, IoringSetupFlags as IoUringSetupFlags,
+        IoringSqFlags as IoUringSqFlags, IoringSqeFlags as IoUringSqeFlags, KernelSigSet, OFlags,
+        Timespec, io_uring_clone_buffers as IoUringCloneBuffers, io_uring_cqe as IoUringCqe,
+        io_uring_enter, io_uring_enter_arg, io_uring_enter_reg_wait,
+        io_uring_getevents_arg as IoUringGeteventsArg, io_uring_napi as IoUringNapi,
+        io_uring_params as IoUringParams, io_uring_ptr as IoUringPtr,
+        io_uring_reg_wait as IoUringRegWait, io_uring_register, io_uring_register_with,
+        io_uring_restriction as IoUringRestriction, io_uring_rsrc_update as IoUringRsrcUpdate,
         io_uring_setup, io_uring_sqe as IoUringSqe, io_uring_user_data as IoUringUserData,
     },
+    thread::CpuSet,
+    time::clock_gettime,
 };
 
 pub const IOURING_MAX_SQ_ENTRIES: u32 = 1 << 15;
@@ -40,6 +51,40 @@ pub struct IoUringWritePi {
     pub rsvd: u64,
 }
 
+// TODO: patch to rustix, `io_uring_probe` uses `IoringOp` which can't hold unknown opcodes
+pub const IOURING_PROBE_OPS: usize = u8::MAX as usize + 1;
+
+#[derive(Debug, Copy, Clone, Default)]
+#[repr(C)]
+pub struct IoUringProbeOp {
+    pub op: u8,
+    pub resv: u8,
+    pub flags: IoUringOpFlags,
+    pub resv2: u32,
+}
+
+#[derive(Debug, Copy, Clone)]
+#[repr(C)]
+pub struct IoUringProbe {
+    pub last_op: u8,
+    pub ops_len: u8,
+    pub resv: u16,
+    pub resv2: [u32; 3],
+    pub ops: [IoUringProbeOp; IOURING_PROBE_OPS],
+}
+
+impl Default for IoUringProbe {
+    fn default() -> Self {
+        Self {
+            last_op: 0,
+            ops_len: 0,
+            resv: 0,
+            resv2: [0; 3],
+            ops: [IoUringProbeOp::default(); IOURING_PROBE_OPS],
+        }
+    }
+}
+
 // TODO: bit flags
 #[derive(Debug, Copy, Clone, Default)]
 pub struct NopFlags {}
@@ -67,3 +112,87 @@ impl NopFlags {
     // IORING_NOP_CQE32
     pub const CQE32: u32 = 1 << 5;
 }
+
+// TODO: patch to rustix
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[repr(u32)]
+pub enum EpollCtlOp {
+    // EPOLL_CTL_ADD
+    Add = 1,
+
+    // EPOLL_CTL_DEL
+    Del = 2,
+
+    // EPOLL_CTL_MOD
+    Mod = 3,
+}
+
+// TODO: patch to rustix
+#[derive(Debug, Copy, Clone, Default)]
+#[repr(C)]
+pub struct IoUringClockRegister {
+    pub clockid: u32,
+    pub resv: [u32; 3],
+}
+
+// TODO: patch to rustix
+// IORING_REGISTER_DST_REPLACE
+pub const IOURING_REGISTER_DST_REPLACE: u32 = 1 << 1;
+
+// TODO: patch to rustix
+#[derive(Debug, Copy, Clone, Default)]
+#[repr(C)]
+pub struct IoUringRegionDesc {
+    pub user_addr: IoUringPtr,
+    pub size: u64,
+    pub flags: u32, // IORING_MEM_REGION_TYPE_USER (1U << 0)
+    pub id: u32,
+    pub mmap_offset: u64,
+    pub resv: [u64; 4],
+}
+
+#[derive(Debug, Copy, Clone, Default)]
+#[repr(C)]
+pub struct IoUringMemRegionReg {
+    pub region_uptr: IoUringPtr,
+    pub flags: u64, // IORING_MEM_REGION_REG_WAIT_ARG (1U << 0)
+    pub resv: [u64; 2],
+}
+
+// IORING_MEM_REGION_TYPE_USER
+pub const IOURING_MEM_REGION_TYPE_USER: u32 = 1 << 0;
+
+// IORING_MEM_REGION_REG_WAIT_ARG
+pub const IOURING_MEM_REGION_REG_WAIT_ARG: u64 = 1 << 0;
+
+// IORING_REG_WAIT_TS
+pub const IOURING_REG_WAIT_TS: u32 = 1 << 0;
+
+// TODO: patch to rustix
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[repr(u8)]
+pub enum NapiOp {
+    // IO_URING_NAPI_REGISTER_OP
+    Register = 0,
+
+    // IO_URING_NAPI_STATIC_ADD_ID
+    StaticAddId = 1,
+
+    // IO_URING_NAPI_STATIC_DEL_ID
+    StaticDelId = 2,
+}
+
+// TODO: patch to rustix
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+#[repr(u32)]
+pub enum NapiTracking {
+    // IO_URING_NAPI_TRACKING_DYNAMIC
+    #[default]
+    Dynamic = 0,
+
+    // IO_URING_NAPI_TRACKING_STATIC
+    Static = 1,
+
+    // IO_URING_NAPI_TRACKING_INACTIVE
+    Inactive = 255,
+}
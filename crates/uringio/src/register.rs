@@ -1,2 +1,12 @@
 pub mod args;
+pub mod buffers;
+pub mod clock;
+pub mod eventfd;
+pub mod iowq;
+pub mod napi;
+pub mod personality;
+pub mod probe;
+pub mod region;
+pub mod resize;
+pub mod restriction;
 pub mod ring_fds;
@@ -0,0 +1,132 @@
+use std::{io::IoSliceMut, ptr::null};
+
+use crate::{
+    platform::iouring::{
+        AsRawFd, IOURING_REGISTER_DST_REPLACE, IoUringCloneBuffers, IoUringRegisterOp,
+    },
+    shared::{
+        error::{Result, err},
+        log::debug,
+        null::{NULL, Null},
+    },
+    uring::enter::UringEnter,
+};
+
+/// ## Clone Buffers
+/// Range of a registered buffer table cloned from another ring, pinned pages are shared
+#[derive(Debug, Default, Copy, Clone)]
+pub struct CloneBuffers {
+    src_off: u32,
+    dst_off: u32,
+    nr: u32,
+    flags: u32,
+}
+
+impl CloneBuffers {
+    /// Whole source table into an empty table
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    /// `nr` buffers from `src_off` of the source table to `dst_off`
+    pub fn range(src_off: u32, dst_off: u32, nr: u32) -> Self {
+        debug_assert!(nr > 0);
+        Self { src_off, dst_off, nr, ..Self::default() }
+    }
+
+    /// Replace existing buffers of the destination table instead of failing with `EBUSY`
+    pub fn replace(mut self) -> Self {
+        self.flags |= IOURING_REGISTER_DST_REPLACE;
+        self
+    }
+}
+
+impl<A, M, S, C> UringEnter<'_, A, M, S, C> {
+    /// Unsafe: buffers must outlive the registration, see `unregister_buffers`
+    pub unsafe fn register_buffers(&self, bufs: &[IoSliceMut<'_>]) -> Result<Null> {
+        let Ok(nr) = u32::try_from(bufs.len()) else {
+            return err!("Too many buffers: {}", bufs.len());
+        };
+
+        debug!("register buffers: {nr}");
+        // SAFETY: IoSliceMut is ABI compatible with iovec
+        unsafe { self.register(IoUringRegisterOp::RegisterBuffers, bufs.as_ptr().cast(), nr)? };
+        Ok(NULL)
+    }
+
+    pub fn unregister_buffers(&self) -> Result<Null> {
+        debug!("unregister buffers");
+        // SAFETY: no arg
+        unsafe { self.register(IoUringRegisterOp::UnregisterBuffers, null(), 0)? };
+        Ok(NULL)
+    }
+
+    /// Clone registered buffers of `src`, which may live on another thread
+    pub fn clone_buffers<A2, M2, S2, C2>(
+        &self,
+        src: &UringEnter<'_, A2, M2, S2, C2>,
+        clone: &CloneBuffers,
+    ) -> Result<Null> {
+        // NOTE: registered ring index is per task, always pass the real fd
+        let mut args = IoUringCloneBuffers::default();
+        args.src_fd = src.ring_fd().as_raw_fd().cast_unsigned();
+        args.flags = clone.flags;
+        args.src_off = clone.src_off;
+        args.dst_off = clone.dst_off;
+        args.nr = clone.nr;
+
+        debug!("clone buffers: {args:?}");
+        // SAFETY: arg points to a single io_uring_clone_buffers
+        let ptr = (&raw const args).cast();
+        unsafe { self.register(IoUringRegisterOp::RegisterCloneBuffers, ptr, 1)? };
+        Ok(NULL)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rustix::io::Errno;
+
+    use super::*;
+    use crate::{
+        completion::entry::Cqe16,
+        operator::noop::Nop,
+        shared::macros::test_uring,
+        submission::{entry::Sqe64, submitter::Submit},
+        uring::{UringIo, mode::Iopoll},
+    };
+
+    fn nop_fixed<A>(uring: &mut UringIo<'_, A, Iopoll>, buf_index: u16) -> i32 {
+        let (enter, mut submitter, mut collector) = uring.borrow();
+        submitter.push(Nop::new().set_buf_index(buf_index)).unwrap();
+        submitter.submit(enter, 1).unwrap();
+
+        collector.update_tail();
+        collector.next().unwrap().res
+    }
+
+    #[test]
+    fn test_clone_buffers() {
+        test_uring!(src = Iopoll::new::<Sqe64, Cqe16>(8));
+
+        let mut bufs = vec![vec![0u8; 4096]; 4];
+        let slices: Vec<_> = bufs.iter_mut().map(|buf| IoSliceMut::new(buf)).collect();
+        unsafe { src.enter.register_buffers(&slices).unwrap() };
+
+        test_uring!(dst = Iopoll::new::<Sqe64, Cqe16>(8));
+
+        dst.enter.clone_buffers(&src.enter, &CloneBuffers::all()).unwrap();
+        assert_eq!(nop_fixed(&mut dst, 3), 0);
+
+        let err = dst.enter.clone_buffers(&src.enter, &CloneBuffers::range(0, 4, 2)).unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(Errno::BUSY.raw_os_error()));
+
+        let clone = CloneBuffers::range(0, 4, 2).replace();
+        dst.enter.clone_buffers(&src.enter, &clone).unwrap();
+        assert_eq!(nop_fixed(&mut dst, 5), 0);
+        assert_eq!(nop_fixed(&mut dst, 6), -Errno::FAULT.raw_os_error());
+
+        src.enter.unregister_buffers().unwrap();
+        assert_eq!(nop_fixed(&mut dst, 0), 0);
+    }
+}
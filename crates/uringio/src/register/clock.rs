@@ -0,0 +1,75 @@
+use std::time::Duration;
+
+use crate::{
+    platform::iouring::{
+        ClockId, IoUringClockRegister, IoUringRegisterOp, Timespec, clock_gettime,
+    },
+    shared::{
+        error::{Result, err},
+        log::debug,
+        null::{NULL, Null},
+    },
+    uring::enter::UringEnter,
+};
+
+impl<A, M, S, C> UringEnter<'_, A, M, S, C> {
+    /// Clock of wait timeouts, `CLOCK_MONOTONIC` by default
+    #[inline]
+    pub fn clock(&self) -> ClockId {
+        self.clock
+    }
+
+    /// Only `CLOCK_MONOTONIC` and `CLOCK_BOOTTIME` are supported
+    pub fn register_clock(&mut self, clock: ClockId) -> Result<Null> {
+        if !matches!(clock, ClockId::Monotonic | ClockId::Boottime) {
+            return err!("Clock {clock:?} Not Supported");
+        }
+
+        let arg = IoUringClockRegister { clockid: clock as _, resv: [0; 3] };
+        debug!("register clock: {clock:?}");
+        // SAFETY: arg points to a single io_uring_clock_register
+        unsafe { self.register(IoUringRegisterOp::RegisterClock, (&raw const arg).cast(), 0)? };
+
+        self.clock = clock;
+        Ok(NULL)
+    }
+
+    /// Current time of the ring clock
+    #[inline]
+    pub fn now(&self) -> Timespec {
+        clock_gettime(self.clock)
+    }
+
+    /// Absolute deadline on the ring clock
+    pub fn deadline(&self, timeout: Duration) -> Result<Timespec> {
+        let Some(deadline) =
+            Timespec::try_from(timeout).ok().and_then(|t| self.now().checked_add(t))
+        else {
+            return err!("Timeout {timeout:?} Overflow");
+        };
+
+        Ok(deadline)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        completion::entry::Cqe16, shared::macros::test_uring, submission::entry::Sqe64,
+        uring::mode::Iopoll,
+    };
+
+    #[test]
+    fn test_register_clock() {
+        test_uring!(uring = Iopoll::new::<Sqe64, Cqe16>(8));
+        assert_eq!(uring.enter.clock(), ClockId::Monotonic);
+
+        assert!(uring.enter.register_clock(ClockId::Realtime).is_err());
+        uring.enter.register_clock(ClockId::Boottime).unwrap();
+        assert_eq!(uring.enter.clock(), ClockId::Boottime);
+
+        let deadline = uring.enter.deadline(Duration::from_secs(1)).unwrap();
+        assert!(deadline > clock_gettime(ClockId::Boottime));
+    }
+}
@@ -0,0 +1,134 @@
+use std::sync::atomic::Ordering;
+
+use rustix::event::{EventfdFlags, eventfd};
+
+use crate::{
+    completion::queue::CompletionQueue,
+    platform::iouring::{
+        AsFd, AsRawFd, BorrowedFd, IoUringCqFlags, IoUringRegisterOp, OwnedFd, c_void,
+    },
+    shared::{
+        error::Result,
+        log::debug,
+        null::{NULL, Null},
+    },
+    uring::enter::UringEnter,
+};
+
+/// ## Eventfd Notifier
+/// Owns the eventfd signaled on posted CQEs
+#[derive(Debug)]
+pub struct EventfdNotifier {
+    fd: OwnedFd,
+    is_async: bool,
+}
+
+impl EventfdNotifier {
+    pub fn new() -> Result<Self> {
+        let fd = eventfd(0, EventfdFlags::CLOEXEC | EventfdFlags::NONBLOCK)?;
+        Ok(Self::from_fd(fd))
+    }
+
+    pub fn from_fd(fd: OwnedFd) -> Self {
+        Self { fd, is_async: false }
+    }
+
+    /// Only signal for completions finished out of line, `IORING_REGISTER_EVENTFD_ASYNC`
+    pub fn async_only(mut self) -> Self {
+        self.is_async = true;
+        self
+    }
+
+    /// Drain the counter, `EAGAIN` if not signaled on a nonblocking eventfd
+    pub fn read(&self) -> Result<u64> {
+        let mut buf = [0; size_of::<u64>()];
+        rustix::io::read(&self.fd, &mut buf)?;
+        Ok(u64::from_ne_bytes(buf))
+    }
+
+    pub fn enable<A, M, S, C>(&self, cq: &CompletionQueue<'_, A, M, S, C>) {
+        cq.remove_flags(IoUringCqFlags::EVENTFD_DISABLED, Ordering::Release);
+    }
+
+    pub fn disable<A, M, S, C>(&self, cq: &CompletionQueue<'_, A, M, S, C>) {
+        cq.insert_flags(IoUringCqFlags::EVENTFD_DISABLED, Ordering::Release);
+    }
+
+    pub fn is_enabled<A, M, S, C>(&self, cq: &CompletionQueue<'_, A, M, S, C>) -> bool {
+        !cq.flags(Ordering::Acquire).contains(IoUringCqFlags::EVENTFD_DISABLED)
+    }
+}
+
+impl AsFd for EventfdNotifier {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.fd.as_fd()
+    }
+}
+
+impl<A, M, S, C> UringEnter<'_, A, M, S, C> {
+    pub fn register_eventfd(&self, notifier: &EventfdNotifier) -> Result<Null> {
+        let op = if notifier.is_async {
+            IoUringRegisterOp::RegisterEventfdAsync
+        } else {
+            IoUringRegisterOp::RegisterEventfd
+        };
+
+        let fd = notifier.fd.as_raw_fd();
+        debug!("register eventfd: {fd}, async: {}", notifier.is_async);
+        // SAFETY: arg points to a single fd
+        unsafe { self.register(op, (&raw const fd).cast(), 1)? };
+        Ok(NULL)
+    }
+
+    pub fn unregister_eventfd(&self) -> Result<Null> {
+        debug!("unregister eventfd");
+        // SAFETY: no arg
+        unsafe {
+            self.register(IoUringRegisterOp::UnregisterEventfd, std::ptr::null::<c_void>(), 0)?
+        };
+        Ok(NULL)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rustix::io::Errno;
+
+    use super::*;
+    use crate::{
+        completion::entry::Cqe16,
+        operator::noop::Nop,
+        shared::macros::test_uring,
+        submission::{entry::Sqe64, submitter::Submit},
+        uring::mode::Iopoll,
+    };
+
+    #[test]
+    fn test_eventfd_notifier() {
+        test_uring!(uring = Iopoll::new::<Sqe64, Cqe16>(8));
+
+        let notifier = EventfdNotifier::new().unwrap();
+        uring.enter.register_eventfd(&notifier).unwrap();
+        assert!(notifier.is_enabled(&uring.cq));
+
+        {
+            let (enter, mut submitter, _) = uring.borrow();
+            submitter.push(Nop::new()).unwrap();
+            submitter.submit(enter, 1).unwrap();
+        }
+        assert_eq!(notifier.read().unwrap(), 1);
+
+        notifier.disable(&uring.cq);
+        assert!(!notifier.is_enabled(&uring.cq));
+
+        {
+            let (enter, mut submitter, _) = uring.borrow();
+            submitter.push(Nop::new()).unwrap();
+            submitter.submit(enter, 1).unwrap();
+        }
+        let err = notifier.read().unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(Errno::AGAIN.raw_os_error()));
+
+        uring.enter.unregister_eventfd().unwrap();
+    }
+}
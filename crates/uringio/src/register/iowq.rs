@@ -0,0 +1,101 @@
+use crate::{
+    platform::iouring::{CpuSet, IoUringRegisterOp, c_void},
+    shared::{
+        error::Result,
+        log::debug,
+        null::{NULL, Null},
+    },
+    uring::enter::UringEnter,
+};
+
+/// ## Io-wq Max Workers
+/// Worker limits, 0 keeps the current limit when set
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+#[repr(C)]
+pub struct IowqMaxWorkers {
+    pub bounded: u32,
+    pub unbounded: u32,
+}
+
+impl IowqMaxWorkers {
+    pub const fn new(bounded: u32, unbounded: u32) -> Self {
+        Self { bounded, unbounded }
+    }
+}
+
+impl<A, M, S, C> UringEnter<'_, A, M, S, C> {
+    /// Shared by rings attached with `UringArgs::attach_wq(&enter.ring_fd())`
+    pub fn register_iowq_aff(&self, cpus: &CpuSet) -> Result<Null> {
+        debug!("register iowq affinity, cpus: {}", cpus.count());
+        // SAFETY: arg points to size_of::<CpuSet>() bytes
+        unsafe {
+            self.register(
+                IoUringRegisterOp::RegisterIowqAff,
+                (&raw const *cpus).cast(),
+                size_of::<CpuSet>() as _,
+            )?
+        };
+        Ok(NULL)
+    }
+
+    pub fn unregister_iowq_aff(&self) -> Result<Null> {
+        debug!("unregister iowq affinity");
+        // SAFETY: no arg
+        unsafe {
+            self.register(IoUringRegisterOp::UnregisterIowqAff, std::ptr::null::<c_void>(), 0)?
+        };
+        Ok(NULL)
+    }
+
+    /// Returns the previous limits
+    pub fn set_iowq_max_workers(&self, limits: IowqMaxWorkers) -> Result<IowqMaxWorkers> {
+        let mut limits = limits;
+        debug!("register iowq max workers: {limits:?}");
+        // SAFETY: arg points to [bounded, unbounded], written back by kernel
+        unsafe {
+            self.register(
+                IoUringRegisterOp::RegisterIowqMaxWorkers,
+                (&raw mut limits).cast_const().cast(),
+                2,
+            )?
+        };
+        Ok(limits)
+    }
+
+    pub fn iowq_max_workers(&self) -> Result<IowqMaxWorkers> {
+        self.set_iowq_max_workers(IowqMaxWorkers::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        completion::entry::Cqe16, platform::iouring::IoUringSetupFlags, shared::macros::test_uring,
+        submission::entry::Sqe64, uring::mode::Sqpoll,
+    };
+
+    #[test]
+    fn test_iowq_shared() {
+        // Limits are per task, attached Sqpoll rings share the SQ thread and its io-wq
+        test_uring!(uring = Sqpoll::new::<Sqe64, Cqe16>(8));
+
+        let mut cpus = CpuSet::new();
+        cpus.set(0);
+        uring.enter.register_iowq_aff(&cpus).unwrap();
+
+        let limits = IowqMaxWorkers::new(2, 4);
+        uring.enter.set_iowq_max_workers(limits).unwrap();
+        assert_eq!(uring.enter.iowq_max_workers().unwrap(), limits);
+
+        let ring_fd = uring.enter.ring_fd();
+        test_uring!(attached = Sqpoll::new::<Sqe64, Cqe16>(8).attach_wq(&ring_fd));
+        assert!(attached.enter.setup_flags().contains(IoUringSetupFlags::ATTACH_WQ));
+        assert_eq!(attached.enter.iowq_max_workers().unwrap(), limits);
+
+        test_uring!(detached = Sqpoll::new::<Sqe64, Cqe16>(8));
+        assert_ne!(detached.enter.iowq_max_workers().unwrap(), limits);
+
+        uring.enter.unregister_iowq_aff().unwrap();
+    }
+}
@@ -0,0 +1,167 @@
+use crate::{
+    platform::iouring::{IoUringNapi, IoUringRegisterOp, IoUringSetupFlags, NapiOp, NapiTracking},
+    shared::{
+        error::{Result, err},
+        log::debug,
+        null::{NULL, Null},
+    },
+    uring::enter::UringEnter,
+};
+
+/// ## Napi
+/// Busy poll settings driven by the ring's wait loop
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct Napi {
+    pub busy_poll_to: u32,
+    pub prefer_busy_poll: bool,
+    pub tracking: NapiTracking,
+}
+
+impl Napi {
+    /// Busy poll timeout in usec, dynamic tracking by default
+    pub fn new(busy_poll_to: u32) -> Self {
+        Self { busy_poll_to, ..Default::default() }
+    }
+
+    pub fn prefer_busy_poll(mut self) -> Self {
+        self.prefer_busy_poll = true;
+        self
+    }
+
+    /// Only explicitly added NAPI ids are polled
+    pub fn static_tracking(mut self) -> Self {
+        self.tracking = NapiTracking::Static;
+        self
+    }
+
+    fn to_raw(self) -> IoUringNapi {
+        let mut raw = IoUringNapi::default();
+        raw.busy_poll_to = self.busy_poll_to;
+        raw.prefer_busy_poll = self.prefer_busy_poll.into();
+        raw.opcode = NapiOp::Register as _;
+        raw.op_param = self.tracking as _;
+        raw
+    }
+}
+
+impl<A, M, S, C> UringEnter<'_, A, M, S, C> {
+    /// Returns the previous busy poll timeout and preference
+    pub fn register_napi(&self, napi: &Napi) -> Result<Napi> {
+        if self.setup_flags.contains(IoUringSetupFlags::IOPOLL) {
+            return err!("Napi Not Supported with IORING_SETUP_IOPOLL");
+        }
+
+        let mut raw = napi.to_raw();
+        debug!("register napi: {napi:?}");
+        // SAFETY: arg points to a single io_uring_napi, written back by kernel
+        unsafe {
+            self.register(IoUringRegisterOp::RegisterNapi, (&raw mut raw).cast_const().cast(), 1)?
+        };
+
+        Ok(Napi {
+            busy_poll_to: raw.busy_poll_to,
+            prefer_busy_poll: raw.prefer_busy_poll != 0,
+            tracking: match raw.op_param {
+                0 => NapiTracking::Dynamic,
+                1 => NapiTracking::Static,
+                _ => NapiTracking::Inactive,
+            },
+        })
+    }
+
+    pub fn unregister_napi(&self) -> Result<Null> {
+        let mut raw = IoUringNapi::default();
+        debug!("unregister napi");
+        // SAFETY: arg points to a single io_uring_napi, written back by kernel
+        unsafe {
+            self.register(IoUringRegisterOp::UnregisterNapi, (&raw mut raw).cast_const().cast(), 1)?
+        };
+        Ok(NULL)
+    }
+
+    fn update_napi_id(&self, op: NapiOp, id: u32) -> Result<Null> {
+        let mut raw = IoUringNapi::default();
+        raw.opcode = op as _;
+        raw.op_param = id;
+        debug!("napi {op:?}: {id}");
+        // SAFETY: arg points to a single io_uring_napi, written back by kernel
+        unsafe {
+            self.register(IoUringRegisterOp::RegisterNapi, (&raw mut raw).cast_const().cast(), 1)?
+        };
+        Ok(NULL)
+    }
+
+    /// Requires static tracking
+    pub fn add_napi_id(&self, id: u32) -> Result<Null> {
+        self.update_napi_id(NapiOp::StaticAddId, id)
+    }
+
+    /// Requires static tracking
+    pub fn del_napi_id(&self, id: u32) -> Result<Null> {
+        self.update_napi_id(NapiOp::StaticDelId, id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::Write,
+        net::{TcpListener, TcpStream},
+        time::Duration,
+    };
+
+    use super::*;
+    use crate::{
+        completion::entry::Cqe16,
+        operator::{OpOutput, read::Read},
+        shared::macros::test_uring,
+        submission::{entry::Sqe64, submitter::Submit},
+        uring::mode::Sqpoll,
+    };
+
+    #[test]
+    fn test_napi() {
+        test_uring!(uring = Sqpoll::new::<Sqe64, Cqe16>(8));
+
+        let napi = Napi::new(50).prefer_busy_poll();
+        uring.enter.register_napi(&napi).unwrap();
+
+        let prev = uring.enter.register_napi(&Napi::new(100).static_tracking()).unwrap();
+        assert_eq!(prev.busy_poll_to, napi.busy_poll_to);
+        assert!(prev.prefer_busy_poll);
+        assert_eq!(prev.tracking, NapiTracking::Dynamic);
+
+        // Ids below MIN_NAPI_ID (NR_CPUS + 1) are sender cpus, not NAPI ids
+        assert!(uring.enter.add_napi_id(0).is_err());
+
+        let id = 1 << 31;
+        uring.enter.add_napi_id(id).unwrap();
+        uring.enter.del_napi_id(id).unwrap();
+        assert!(uring.enter.del_napi_id(id).is_err());
+
+        uring.enter.unregister_napi().unwrap();
+    }
+
+    #[test]
+    fn test_napi_loopback() {
+        test_uring!(uring = Sqpoll::new::<Sqe64, Cqe16>(8));
+        uring.enter.register_napi(&Napi::new(50)).unwrap();
+
+        // NOTE: loopback has no NAPI instance, busy poll loop must still deliver completions.
+        // veth pairs need CAP_NET_ADMIN and are not covered here
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (server, _) = listener.accept().unwrap();
+
+        let mut buf = [0; 4];
+        let (enter, mut submitter, mut collector) = uring.borrow();
+        submitter.push(Read::new(&server, &mut buf).user_data(1)).unwrap();
+        submitter.submit(enter).unwrap();
+        client.write_all(b"napi").unwrap();
+
+        collector.wait(enter, 1, Duration::from_secs(1)).unwrap();
+        let cqe = collector.next().unwrap();
+        assert_eq!(Read::output(cqe).unwrap(), 4);
+        assert_eq!(&buf, b"napi");
+    }
+}
@@ -0,0 +1,118 @@
+use crate::{
+    platform::iouring::{AsRawFd, BorrowedFd, IoUringRegisterOp, c_void, io_uring_register},
+    shared::{
+        error::{Result, err},
+        log::debug,
+    },
+    uring::enter::UringEnter,
+};
+
+/// ## Personality Id
+/// Sqe personality, 0 uses the submitter's credentials
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+#[repr(transparent)]
+pub struct PersonalityId(u16);
+
+impl PersonalityId {
+    #[inline]
+    pub const fn id(&self) -> u16 {
+        self.0
+    }
+}
+
+/// ## Personality
+/// Registered credentials of the current task, unregistered on drop
+#[derive(Debug)]
+pub struct Personality<'fd> {
+    ring_fd: BorrowedFd<'fd>,
+    id: PersonalityId,
+}
+
+impl Personality<'_> {
+    #[inline]
+    pub const fn id(&self) -> PersonalityId {
+        self.id
+    }
+}
+
+impl From<&Personality<'_>> for PersonalityId {
+    #[inline]
+    fn from(personality: &Personality<'_>) -> Self {
+        personality.id
+    }
+}
+
+impl Drop for Personality<'_> {
+    fn drop(&mut self) {
+        debug!("unregister personality: {}", self.id.0);
+        // SAFETY: no arg, nr_args is the personality id
+        let _ = unsafe {
+            io_uring_register(
+                self.ring_fd,
+                IoUringRegisterOp::UnregisterPersonality,
+                std::ptr::null::<c_void>(),
+                self.id.0.into(),
+            )
+        };
+    }
+}
+
+impl<'fd, A, M, S, C> UringEnter<'fd, A, M, S, C> {
+    pub fn register_personality(&self) -> Result<Personality<'fd>> {
+        // SAFETY: no arg
+        let id = unsafe {
+            self.register(IoUringRegisterOp::RegisterPersonality, std::ptr::null::<c_void>(), 0)?
+        };
+        debug!("register personality: {id}, ring fd: {}", self.ring_fd.as_raw_fd());
+
+        // NOTE: kernel allocates ids in 1..=u16::MAX
+        let Ok(id) = u16::try_from(id) else {
+            return err!("Invalid personality id: {id}");
+        };
+        Ok(Personality { ring_fd: self.ring_fd, id: PersonalityId(id) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rustix::io::Errno;
+
+    use super::*;
+    use crate::{
+        completion::entry::Cqe16,
+        operator::noop::Nop,
+        shared::macros::test_uring,
+        submission::{entry::Sqe64, submitter::Submit},
+        uring::mode::Iopoll,
+    };
+
+    #[test]
+    fn test_personality() {
+        test_uring!(uring = Iopoll::new::<Sqe64, Cqe16>(8));
+        let mut uring = uring.register().unwrap();
+
+        let personality = uring.enter.register_personality().unwrap();
+        assert_ne!(personality.id(), PersonalityId::default());
+        let stale = personality.id();
+
+        {
+            let (enter, mut submitter, mut collector) = uring.borrow();
+            submitter.push(Nop::new().personality(&personality).user_data(1)).unwrap();
+            submitter.submit(enter, 1).unwrap();
+
+            collector.update_tail();
+            assert_eq!(collector.next().unwrap().res, 0);
+        }
+
+        drop(personality);
+
+        let (enter, mut submitter, mut collector) = uring.borrow();
+        let mut nop = Nop::new().user_data(2);
+        nop.personality = stale;
+        submitter.push(nop).unwrap();
+        submitter.submit(enter, 1).unwrap();
+
+        collector.update_tail();
+        assert_eq!(collector.next().unwrap().res, -Errno::INVAL.raw_os_error());
+    }
+}
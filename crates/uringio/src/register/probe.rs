@@ -0,0 +1,132 @@
+use crate::{
+    operator::Op,
+    platform::iouring::{
+        IOURING_PROBE_OPS, IoUringFeatureFlags, IoUringOp, IoUringOpFlags, IoUringProbe,
+        IoUringRegisterOp, IoUringSetupFlags,
+    },
+    shared::{
+        error::{Result, err},
+        log::debug,
+        null::{NULL, Null},
+    },
+    uring::enter::UringEnter,
+};
+
+/// ## Probe
+/// Capability report of the running kernel and ring
+#[derive(Debug, Clone)]
+pub struct Probe {
+    last_op: u8,
+    ops: [IoUringOpFlags; IOURING_PROBE_OPS],
+    setup_flags: IoUringSetupFlags,
+    features: IoUringFeatureFlags,
+}
+
+impl Probe {
+    /// Last opcode known to the kernel
+    #[inline]
+    pub fn last_op(&self) -> u8 {
+        self.last_op
+    }
+
+    #[inline]
+    pub fn setup_flags(&self) -> &IoUringSetupFlags {
+        &self.setup_flags
+    }
+
+    #[inline]
+    pub fn features(&self) -> &IoUringFeatureFlags {
+        &self.features
+    }
+
+    #[inline]
+    pub fn is_supported(&self, op: IoUringOp) -> bool {
+        self.ops[op as usize].contains(IoUringOpFlags::SUPPORTED)
+    }
+
+    /// Supported raw opcodes
+    pub fn supported_ops(&self) -> impl Iterator<Item = u8> {
+        (0..=self.last_op).filter(|&op| self.ops[op as usize].contains(IoUringOpFlags::SUPPORTED))
+    }
+
+    #[inline]
+    pub fn supports<T: Op>(&self) -> bool {
+        self.is_supported(T::OP_CODE)
+    }
+
+    pub fn check<T: Op>(&self) -> Result<Null> {
+        if !self.supports::<T>() {
+            return err!("Opcode {:?} Not Supported", T::OP_CODE);
+        }
+
+        Ok(NULL)
+    }
+}
+
+impl<A, M, S, C> UringEnter<'_, A, M, S, C> {
+    pub fn probe(&self) -> Result<Probe> {
+        // NOTE: kernel rejects non-zeroed probe
+        let mut probe = Box::new(IoUringProbe::default());
+        let ptr = (&raw mut *probe).cast();
+
+        // SAFETY: probe holds IOURING_PROBE_OPS ops
+        let nr_ops = u32::from(u8::MAX) + 1;
+        unsafe { self.register(IoUringRegisterOp::RegisterProbe, ptr, nr_ops)? };
+        debug!("probe last op: {}, ops len: {}", probe.last_op, probe.ops_len);
+
+        let mut ops = [IoUringOpFlags::empty(); IOURING_PROBE_OPS];
+        for op in &probe.ops[..probe.ops_len as usize] {
+            ops[op.op as usize] = op.flags;
+        }
+
+        Ok(Probe {
+            last_op: probe.last_op,
+            ops,
+            setup_flags: self.setup_flags,
+            features: self.features,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        completion::entry::Cqe16,
+        operator::{epoll::EpollWait, noop::Nop, pipe::Pipe, read::Read},
+        shared::macros::test_uring,
+        submission::{entry::Sqe64, submitter::Submit},
+        uring::mode::Iopoll,
+    };
+
+    #[test]
+    fn test_probe() {
+        test_uring!(uring = Iopoll::new::<Sqe64, Cqe16>(8));
+        let uring = uring.register().unwrap();
+
+        let probe = uring.enter.probe().unwrap();
+        assert!(probe.last_op() >= IoUringOp::Read as u8);
+        assert!(probe.check::<Nop>().is_ok());
+        assert!(probe.supports::<Read>());
+        assert!(probe.supported_ops().any(|op| op == IoUringOp::Nop as u8));
+        // Since Linux 6.15 and 6.16
+        assert!(probe.supports::<EpollWait>());
+        assert!(probe.supports::<Pipe>());
+        assert!(probe.setup_flags().contains(IoUringSetupFlags::IOPOLL));
+    }
+
+    #[test]
+    fn test_probe_push() {
+        test_uring!(uring = Iopoll::new::<Sqe64, Cqe16>(8));
+        let mut uring = uring.probe().unwrap();
+        assert!(uring.submitter().push(Nop::new()).is_ok());
+
+        // Unsupported op handed back for a fallback
+        if let Some(probe) = &mut uring.sq.probe {
+            probe.ops[IoUringOp::Nop as usize] = IoUringOpFlags::empty();
+        }
+        assert!(!uring.sq.supports(IoUringOp::Nop));
+        assert!(uring.submitter().push(Nop::new()).is_err());
+        assert_eq!(uring.submitter().size(), 1);
+    }
+}
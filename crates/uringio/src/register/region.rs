@@ -0,0 +1,147 @@
+use std::{marker::PhantomData, ptr::null_mut};
+
+use crate::{
+    platform::{
+        iouring::{
+            IOURING_MEM_REGION_REG_WAIT_ARG, IOURING_MEM_REGION_TYPE_USER, IOURING_REG_WAIT_TS,
+            IoUringEnterFlags, IoUringMemRegionReg, IoUringPtr, IoUringRegWait, IoUringRegionDesc,
+            IoUringRegisterOp, KernelSigSet, io_uring_enter_reg_wait,
+        },
+        mmap::{MapFlags, Mmap, ProtFlags, page_align},
+    },
+    shared::{
+        error::{Result, err},
+        log::debug,
+        null::{NULL, Null},
+    },
+    uring::{enter::UringEnter, wait::WaitArg},
+};
+
+/// ## Wait Region
+/// Registered `io_uring_reg_wait` slots, waits pass a slot index instead of copying a [`WaitArg`]
+#[derive(Debug)]
+pub struct WaitRegion<'a> {
+    mmap: Mmap,
+    len: usize,
+
+    _marker_: PhantomData<&'a KernelSigSet>,
+}
+
+impl<'a> WaitRegion<'a> {
+    pub fn new(len: usize) -> Result<Self> {
+        debug_assert!(len > 0);
+
+        let size = page_align(len * size_of::<IoUringRegWait>());
+        let prot = ProtFlags::READ | ProtFlags::WRITE;
+        let mmap = unsafe { Mmap::mmap_anonymous(null_mut(), size, prot, MapFlags::PRIVATE)? };
+        let len = size / size_of::<IoUringRegWait>();
+
+        Ok(Self { mmap, len, _marker_: PhantomData })
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Write `arg` into slot `idx`, slots hold relative timeouts only
+    pub fn set(&mut self, idx: usize, arg: &WaitArg<'a>) -> Result<Null> {
+        if idx >= self.len {
+            return err!("Wait region index {idx} exceeds len: {}", self.len);
+        }
+
+        if arg.abs {
+            return err!("Wait region slot {idx} takes a relative timeout only");
+        }
+
+        let mut slot = IoUringRegWait::default();
+        if let Some(ts) = arg.ts {
+            slot.ts = ts;
+            slot.flags = IOURING_REG_WAIT_TS;
+        }
+        if let Some(sigmask) = arg.sigmask {
+            slot.sigmask = IoUringPtr::new((&raw const *sigmask).cast_mut().cast());
+            slot.sigmask_sz = size_of::<KernelSigSet>() as _;
+        }
+        slot.min_wait_usec = arg.min_wait_usec;
+
+        // SAFETY: idx checked
+        unsafe { self.mmap.ptr().cast::<IoUringRegWait>().add(idx).write(slot) };
+        Ok(NULL)
+    }
+}
+
+impl<A, M, S, C> UringEnter<'_, A, M, S, C> {
+    /// Must be registered before `enable_rings` on a `R_DISABLED` ring
+    pub fn register_wait_region(&self, region: &WaitRegion<'_>) -> Result<Null> {
+        let mut desc = IoUringRegionDesc {
+            user_addr: IoUringPtr::new(region.mmap.ptr().as_ptr()),
+            size: region.mmap.len() as _,
+            flags: IOURING_MEM_REGION_TYPE_USER,
+            ..Default::default()
+        };
+        let reg = IoUringMemRegionReg {
+            region_uptr: IoUringPtr::new((&raw mut desc).cast()),
+            flags: IOURING_MEM_REGION_REG_WAIT_ARG,
+            ..Default::default()
+        };
+
+        debug!("register wait region: {desc:?}");
+        // SAFETY: arg points to a single io_uring_mem_region_reg
+        unsafe { self.register(IoUringRegisterOp::RegisterMemRegion, (&raw const reg).cast(), 1)? };
+        Ok(NULL)
+    }
+
+    /// Unsafe: slot `idx` of the registered region must hold a valid wait arg
+    pub unsafe fn enter_reg_wait(
+        &self,
+        to_submit: u32,
+        min_complete: u32,
+        flags: IoUringEnterFlags,
+        idx: usize,
+    ) -> Result<u32> {
+        // NOTE: kernel only reads the region offset with EXT_ARG set
+        let offset = idx * size_of::<IoUringRegWait>();
+        let flags =
+            self.enter_flags | flags | IoUringEnterFlags::EXT_ARG | IoUringEnterFlags::EXT_ARG_REG;
+        Ok(unsafe {
+            io_uring_enter_reg_wait(self.enter_fd, to_submit, min_complete, flags, offset)?
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use rustix::io::Errno;
+
+    use super::*;
+    use crate::{
+        completion::entry::Cqe16, platform::iouring::Timespec, shared::macros::test_uring,
+        submission::entry::Sqe64, uring::mode::Sqpoll,
+    };
+
+    #[test]
+    fn test_wait_region() {
+        test_uring!(uring = Sqpoll::new::<Sqe64, Cqe16>(8).r_disabled());
+
+        let mut region = WaitRegion::new(4).unwrap();
+        assert!(region.len() >= 4);
+        region.set(1, &WaitArg::new().timeout(Duration::from_millis(10))).unwrap();
+        assert!(region.set(region.len(), &WaitArg::new()).is_err());
+        assert!(region.set(0, &WaitArg::new().deadline(Timespec::default())).is_err());
+
+        uring.enter.register_wait_region(&region).unwrap();
+        uring.enter.enable_rings().unwrap();
+
+        let flags = IoUringEnterFlags::GETEVENTS;
+        let err = unsafe { uring.enter.enter_reg_wait(0, 1, flags, 1) }.unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(Errno::TIME.raw_os_error()));
+    }
+}
@@ -0,0 +1,225 @@
+use crate::{
+    arena::Arena,
+    completion::{entry::Cqe, queue::CompletionQueue},
+    platform::iouring::{IoUringParams, IoUringRegisterOp, IoUringSetupFlags},
+    shared::{
+        error::{Result, err},
+        log::debug,
+        null::{NULL, Null},
+    },
+    submission::{backlog::Backlog, entry::Sqe, queue::SubmissionQueue},
+    uring::{Uring, args::UringArgs, enter::UringEnter, mode::Mode},
+};
+
+impl<A, M, S, C> UringEnter<'_, A, M, S, C> {
+    /// Kernel only accepts `IORING_SETUP_CQSIZE | IORING_SETUP_CLAMP`, others are inherited
+    pub(crate) fn resize_rings(&self, args: &mut UringArgs<A, M, S, C>) -> Result<Null> {
+        let flags = args.params.flags;
+        args.params.flags &= IoUringSetupFlags::CQSIZE | IoUringSetupFlags::CLAMP;
+
+        debug!("resize rings: {:?}", args.params);
+        // SAFETY: arg points to a single io_uring_params, written back by kernel
+        let res = unsafe {
+            self.register(
+                IoUringRegisterOp::RegisterResizeRings,
+                (&raw mut args.params).cast_const().cast(),
+                1,
+            )
+        };
+        args.params.flags |= flags;
+        res?;
+
+        Ok(NULL)
+    }
+}
+
+impl<A, M, S, C> Uring<'_, A, M, S, C>
+where
+    A: Arena<M, S, C>,
+    M: Mode,
+    S: Sqe,
+    C: Cqe,
+{
+    /// Resize rings, pending CQEs and published SQEs are copied by ring position.
+    /// With an SQ array the SQ must be empty, the kernel ignores the array while copying
+    ///
+    /// Requires `IORING_SETUP_DEFER_TASKRUN`, `&mut self` ensures no live `Submitter` or `Collector`.
+    /// If remapping fails after the kernel switched rings, the queues are poisoned:
+    /// pushes fail and nothing is collected until a later resize succeeds
+    pub fn resize(&mut self, sq_entries: u32, cq_entries: u32) -> Result<Null> {
+        if !self.enter.setup_flags.contains(IoUringSetupFlags::DEFER_TASKRUN) {
+            return err!("Resize requires IORING_SETUP_DEFER_TASKRUN");
+        }
+
+        if self.sq.has_array() && self.sq.tail() != self.sq.head() {
+            return err!("Resize with SQ array requires an empty SQ");
+        }
+
+        // Backlog chains are never split
+        let chain = self.sq.backlog.as_ref().map_or(0, Backlog::max_chain);
+        if chain > sq_entries.next_power_of_two() {
+            return err!("Resize below backlog chain of {chain} slots");
+        }
+
+        let mut params = IoUringParams::default();
+        params.sq_entries = sq_entries;
+        params.cq_entries = cq_entries;
+        params.flags =
+            self.enter.setup_flags | IoUringSetupFlags::CQSIZE | IoUringSetupFlags::CLAMP;
+        let mut args = UringArgs::from(params);
+
+        // Old rings stay in use on failure
+        let arena = A::prepare_resize(&mut args)?;
+        self.enter.resize_rings(&mut args)?;
+
+        let arena = match A::finish_resize(&self.enter, &args, arena) {
+            Ok(arena) => arena,
+            Err(err) => {
+                debug!("remap resized rings failed, poison queues: {err}");
+                self.sq.poisoned = true;
+                self.cq.poisoned = true;
+                return Err(err);
+            },
+        };
+
+        // SAFETY: rebuild queues on the remapped arena before the old one unmaps
+        let (mut sq, mut cq) =
+            unsafe { (SubmissionQueue::new(&arena, &args), CompletionQueue::new(&arena, &args)) };
+        // Backlog and overflow counters outlive the rings
+        (sq.backlog, sq.throttled, sq.flushes) =
+            (self.sq.backlog.take(), self.sq.throttled, self.sq.flushes);
+        cq.flushes = self.cq.flushes;
+        (self.sq, self.cq) = (sq, cq);
+        self.arena = arena;
+        debug!("resized rings, sq: {}, cq: {}", self.sq.size, self.cq.size);
+
+        Ok(NULL)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        completion::entry::Cqe16,
+        operator::noop::Nop,
+        shared::macros::test_uring,
+        submission::{entry::Sqe64, submitter::Submit},
+        uring::{UringIo, mode::Iopoll},
+    };
+
+    fn resize_and_submit<A>(mut uring: UringIo<'_, A, Iopoll>)
+    where
+        A: Arena<Iopoll, Sqe64, Cqe16>,
+    {
+        assert_eq!(uring.sq.size, 8);
+
+        // pending sqes are copied to the new rings
+        let mut submitter = uring.submitter();
+        submitter.push(Nop::new().user_data(1)).unwrap();
+        submitter.push(Nop::new().user_data(2)).unwrap();
+        drop(submitter);
+
+        uring.resize(32, 64).unwrap();
+        assert_eq!(uring.sq.size, 32);
+        assert_eq!(uring.cq.size, 64);
+
+        let (enter, mut submitter, mut collector) = uring.borrow();
+        for user_data in 3..=32 {
+            submitter.push(Nop::new().user_data(user_data)).unwrap();
+        }
+        assert!(submitter.is_full());
+        assert_eq!(submitter.submit(enter, 32).unwrap(), 32);
+
+        collector.update_tail();
+        let user_data: Vec<_> = collector.map(|cqe| cqe.user_data.u64_()).collect();
+        assert_eq!(user_data, (1..=32).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_resize_mmap_arena() {
+        test_uring!(uring = Iopoll::new::<Sqe64, Cqe16>(8));
+        resize_and_submit(uring);
+    }
+
+    #[test]
+    fn test_resize_sqarray_pending() {
+        test_uring!(uring = Iopoll::new::<Sqe64, Cqe16>(8).sqarray());
+
+        let mut submitter = uring.submitter();
+        let slot = submitter.reserve(Nop::new().user_data(1)).unwrap();
+        submitter.push(Nop::new().user_data(2)).unwrap();
+        submitter.commit(slot).unwrap();
+        drop(submitter);
+        assert!(uring.resize(16, 32).is_err());
+
+        {
+            let (enter, mut submitter, mut collector) = uring.borrow();
+            assert_eq!(submitter.submit(enter, 2).unwrap(), 2);
+            collector.update_tail();
+            assert_eq!(collector.count(), 2);
+        }
+        uring.resize(16, 32).unwrap();
+        assert_eq!(uring.sq.size, 16);
+    }
+
+    #[test]
+    fn test_resize_poisoned() {
+        test_uring!(uring = Iopoll::new::<Sqe64, Cqe16>(8));
+        uring.sq.poisoned = true;
+        uring.cq.poisoned = true;
+
+        {
+            let (enter, mut submitter, mut collector) = uring.borrow();
+            assert!(submitter.is_full());
+            assert!(submitter.push(Nop::new()).is_err());
+            submitter.submit(enter, 0).unwrap();
+            collector.update_tail();
+            assert_eq!(collector.size(), 0);
+        }
+
+        // A later resize remaps and clears the poison
+        uring.resize(16, 32).unwrap();
+        assert!(!uring.sq.is_poisoned() && !uring.cq.is_poisoned());
+        assert!(uring.submitter().push(Nop::new()).is_ok());
+    }
+
+    #[test]
+    fn test_resize_backlog() {
+        test_uring!(uring = Iopoll::new::<Sqe64, Cqe16>(4));
+        let mut uring = uring.backlog();
+        (uring.sq.throttled, uring.sq.flushes, uring.cq.flushes) = (1, 2, 3);
+
+        {
+            let mut submitter = uring.submitter();
+            for user_data in 1..5 {
+                submitter.push(Nop::new().user_data(user_data)).unwrap();
+            }
+            submitter.push(Nop::new().user_data(5).io_link()).unwrap();
+            submitter.push(Nop::new().user_data(6).io_link()).unwrap();
+            submitter.push(Nop::new().user_data(7)).unwrap();
+            assert_eq!(submitter.backlog_len(), 3);
+        }
+
+        // Chain of 3 never fits a SQ of 2
+        assert!(uring.resize(2, 4).is_err());
+        uring.resize(8, 16).unwrap();
+        assert_eq!(uring.sq.backlog_len(), 3);
+        assert_eq!((uring.sq.throttled(), uring.overflow_stats().flushes), (1, 5));
+
+        let (enter, mut submitter, mut collector) = uring.borrow();
+        let mut user_datas = Vec::new();
+        while user_datas.len() < 7 {
+            submitter.submit(enter, 0).unwrap();
+            collector.update();
+            user_datas.extend(collector.by_ref().map(|cqe| cqe.user_data.u64_()));
+        }
+        assert_eq!(user_datas, (1..8).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_resize_huge_arena() {
+        test_uring!(uring = Iopoll::new::<Sqe64, Cqe16>(8).no_mmap());
+        resize_and_submit(uring);
+    }
+}
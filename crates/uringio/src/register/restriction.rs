@@ -0,0 +1,154 @@
+use crate::{
+    operator::Op,
+    platform::iouring::{
+        IoUringOp, IoUringRegisterOp, IoUringRestriction, IoUringRestrictionOp, IoUringSetupFlags,
+        IoUringSqeFlags, c_void,
+    },
+    shared::{
+        error::{Result, err},
+        log::debug,
+        null::{NULL, Null},
+    },
+    uring::enter::UringEnter,
+};
+
+#[derive(Debug, Copy, Clone)]
+pub enum Restriction {
+    RegisterOp(IoUringRegisterOp),
+    SqeOp(IoUringOp),
+    SqeFlagsAllowed(IoUringSqeFlags),
+    SqeFlagsRequired(IoUringSqeFlags),
+}
+
+impl From<Restriction> for IoUringRestriction {
+    fn from(restriction: Restriction) -> Self {
+        let mut this = Self::default();
+        match restriction {
+            Restriction::RegisterOp(op) => {
+                this.opcode = IoUringRestrictionOp::RegisterOp;
+                this.register_or_sqe_op_or_sqe_flags.register_op = op;
+            },
+            Restriction::SqeOp(op) => {
+                this.opcode = IoUringRestrictionOp::SqeOp;
+                this.register_or_sqe_op_or_sqe_flags.sqe_op = op;
+            },
+            Restriction::SqeFlagsAllowed(flags) => {
+                this.opcode = IoUringRestrictionOp::SqeFlagsAllowed;
+                this.register_or_sqe_op_or_sqe_flags.sqe_flags = flags;
+            },
+            Restriction::SqeFlagsRequired(flags) => {
+                this.opcode = IoUringRestrictionOp::SqeFlagsRequired;
+                this.register_or_sqe_op_or_sqe_flags.sqe_flags = flags;
+            },
+        }
+        this
+    }
+}
+
+/// ## Restrictions
+/// Allow list applied to a `R_DISABLED` ring before [`UringEnter::enable_rings`]
+#[derive(Debug, Clone, Default)]
+pub struct Restrictions {
+    entries: Vec<Restriction>,
+}
+
+impl Restrictions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn allow_register_op(mut self, op: IoUringRegisterOp) -> Self {
+        self.entries.push(Restriction::RegisterOp(op));
+        self
+    }
+
+    pub fn allow_sqe_op(mut self, op: IoUringOp) -> Self {
+        self.entries.push(Restriction::SqeOp(op));
+        self
+    }
+
+    pub fn allow<T: Op>(self) -> Self {
+        self.allow_sqe_op(T::OP_CODE)
+    }
+
+    pub fn allow_sqe_flags(mut self, flags: IoUringSqeFlags) -> Self {
+        self.entries.push(Restriction::SqeFlagsAllowed(flags));
+        self
+    }
+
+    pub fn require_sqe_flags(mut self, flags: IoUringSqeFlags) -> Self {
+        self.entries.push(Restriction::SqeFlagsRequired(flags));
+        self
+    }
+
+    #[inline]
+    pub fn entries(&self) -> &[Restriction] {
+        &self.entries
+    }
+}
+
+impl<A, M, S, C> UringEnter<'_, A, M, S, C> {
+    pub fn register_restrictions(&self, restrictions: &Restrictions) -> Result<Null> {
+        if !self.setup_flags.contains(IoUringSetupFlags::R_DISABLED) {
+            return err!("Restrictions require IORING_SETUP_R_DISABLED");
+        }
+
+        let raw: Vec<IoUringRestriction> = restrictions.entries.iter().map(|&r| r.into()).collect();
+        debug!("register restrictions: {:?}", restrictions.entries);
+        // SAFETY: arg points to raw.len() restrictions
+        unsafe {
+            self.register(
+                IoUringRegisterOp::RegisterRestrictions,
+                raw.as_ptr().cast(),
+                raw.len() as _,
+            )?
+        };
+        Ok(NULL)
+    }
+
+    pub fn enable_rings(&self) -> Result<Null> {
+        debug!("enable rings");
+        // SAFETY: no arg
+        unsafe {
+            self.register(IoUringRegisterOp::RegisterEnableRings, std::ptr::null::<c_void>(), 0)?
+        };
+        Ok(NULL)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rustix::io::Errno;
+
+    use super::*;
+    use crate::{
+        completion::entry::Cqe16,
+        operator::{noop::Nop, read::Read},
+        submission::{entry::Sqe64, submitter::Submit},
+        uring::{UringIo, mode::Iopoll},
+    };
+
+    #[test]
+    fn test_restrictions() {
+        let (fd, args, arena) = Iopoll::new::<Sqe64, Cqe16>(8).r_disabled().setup().unwrap();
+        let mut uring = UringIo::new(&fd, &args, arena).unwrap();
+
+        let restrictions =
+            Restrictions::new().allow::<Nop>().allow_sqe_flags(IoUringSqeFlags::CQE_SKIP_SUCCESS);
+        uring.enter.register_restrictions(&restrictions).unwrap();
+        uring.enter.enable_rings().unwrap();
+
+        // register ops are not allowed after restriction
+        assert!(uring.enter.probe().is_err());
+
+        let mut buf = [0; 8];
+        let (enter, mut submitter, mut collector) = uring.borrow();
+        submitter.push(Nop::new().user_data(1)).unwrap();
+        submitter.push(Read::new(&fd, &mut buf).user_data(2)).unwrap();
+        submitter.submit(enter, 2).unwrap();
+
+        collector.update_tail();
+        let results: Vec<_> = collector.map(|cqe| (cqe.user_data.u64_(), cqe.res)).collect();
+        assert_eq!(results, [(1, 0), (2, -Errno::ACCESS.raw_os_error())]);
+    }
+}
@@ -52,6 +52,6 @@ impl<A, M, S, C> UringEnter<'_, A, M, S, C> {
         let idx = self.enter_fd.as_raw_fd().cast_unsigned();
         debug!("unregister ring id: {idx}");
         let args = IoUringRsrcUpdate::unregister(idx);
-        Ok(unsafe { io_uring_register(self.enter_fd, UnregisterRingFds, args.as_ptr(), 1)? })
+        Ok(unsafe { self.register(UnregisterRingFds, args.as_ptr(), 1)? })
     }
 }
@@ -1 +1,14 @@
 pub use uringio_macro::op;
+
+/// Bind a test ring over `$args` to `$uring`, its fd and args live until the end of the scope
+#[cfg(test)]
+macro_rules! test_uring {
+    ($uring:ident = $args:expr) => {
+        let (fd, args, arena) = $args.setup().unwrap();
+        #[allow(unused_mut)]
+        let mut $uring = $crate::uring::Uring::new(&fd, &args, arena).unwrap();
+    };
+}
+
+#[cfg(test)]
+pub(crate) use test_uring;
@@ -1,3 +1,4 @@
+pub mod backlog;
 pub mod entry;
 pub mod index;
 pub mod queue;
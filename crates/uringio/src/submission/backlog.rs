@@ -0,0 +1,138 @@
+use std::collections::VecDeque;
+
+use crate::{
+    platform::iouring::{IoUringSqe, IoUringSqeFlags},
+    submission::entry::Sqe,
+};
+
+pub(crate) const LINK: IoUringSqeFlags =
+    IoUringSqeFlags::IO_LINK.union(IoUringSqeFlags::IO_HARDLINK);
+
+/// ## Backlog
+/// SQEs pushed beyond SQ capacity, drained into the ring by whole link chains.
+/// 128-byte entries of a mixed queue are kept as 2 halves
+#[derive(Debug, Default)]
+pub struct Backlog<S> {
+    entries: VecDeque<S>,
+    /// Slots of the unterminated chain at back
+    open: u32,
+    /// Unterminated chain holds a 128-byte entry, which may need a padding slot
+    wide: bool,
+}
+
+impl<S> Backlog<S> {
+    pub const fn new() -> Self {
+        Self { entries: VecDeque::new(), open: 0, wide: false }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    #[inline]
+    pub(crate) fn pop(&mut self) -> Option<S> {
+        self.entries.pop_front()
+    }
+
+    #[inline]
+    pub(crate) fn get(&self, idx: usize) -> Option<&S> {
+        self.entries.get(idx)
+    }
+}
+
+impl<S> Backlog<S>
+where
+    S: Sqe,
+{
+    /// Ring slots taken by the unterminated chain with `sqes` appended
+    pub(crate) fn chain_slots<'a>(&self, sqes: impl IntoIterator<Item = &'a IoUringSqe>) -> u32 {
+        let (slots, wide) = sqes.into_iter().fold((self.open, self.wide), |(slots, wide), sqe| {
+            (slots + S::slots(sqe), wide || S::slots(sqe) > 1)
+        });
+        slots + u32::from(wide)
+    }
+
+    /// Whether `sqe` keeps its chain within `size` slots, a linked entry leaves room for the last one
+    pub(crate) fn admits(&self, sqe: &IoUringSqe, size: u32) -> bool {
+        let slots = self.chain_slots([sqe]);
+        if sqe.flags.intersects(LINK) { slots < size } else { slots <= size }
+    }
+
+    /// Push an entry, `hi` is the second half of a 128-byte entry
+    pub(crate) fn push(&mut self, sqe: S, hi: Option<S>) {
+        if sqe.flags.intersects(LINK) {
+            self.open += S::slots(&sqe);
+            self.wide |= S::slots(&sqe) > 1;
+        } else {
+            (self.open, self.wide) = (0, false);
+        }
+
+        self.entries.push_back(sqe);
+        self.entries.extend(hi);
+    }
+
+    /// Length of the link chain at front, `None` until its last entry is pushed
+    pub(crate) fn front_chain(&self) -> Option<usize> {
+        let mut idx = 0;
+        while let Some(sqe) = self.entries.get(idx) {
+            idx += S::slots(sqe) as usize;
+            if !sqe.flags.intersects(LINK) {
+                return Some(idx);
+            }
+        }
+
+        None
+    }
+
+    /// Ring slots taken by the longest chain, padding included
+    pub(crate) fn max_chain(&self) -> u32 {
+        let (mut max, mut slots, mut wide, mut idx) = (0, 0, false, 0);
+        while let Some(sqe) = self.entries.get(idx) {
+            let len = S::slots(sqe);
+            (idx, slots) = (idx + len as usize, slots + len);
+            wide |= len > 1;
+            max = max.max(slots + u32::from(wide));
+            if !sqe.flags.intersects(LINK) {
+                (slots, wide) = (0, false);
+            }
+        }
+
+        max
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        operator::noop::{Nop, Nop128},
+        submission::entry::SqeMix,
+    };
+
+    #[test]
+    fn test_backlog_mixed_chain() {
+        let mut backlog = Backlog::<SqeMix>::new();
+        let wide = || (Nop128::new().io_link().into(), Some(Nop::new().into()));
+
+        let (lo, hi) = wide();
+        backlog.push(lo, hi);
+        assert_eq!(backlog.front_chain(), None);
+
+        // 128-byte entry may need a padding slot
+        let last = SqeMix::from(Nop::new());
+        assert!(!backlog.admits(&SqeMix::from(Nop::new().io_link()), 4));
+        assert!(backlog.admits(&last, 4));
+        assert!(!backlog.admits(&last, 3));
+
+        backlog.push(last, None);
+        assert_eq!((backlog.len(), backlog.front_chain()), (3, Some(3)));
+        assert_eq!(backlog.max_chain(), 4);
+        assert!(backlog.admits(&SqeMix::from(Nop::new().io_link()), 2));
+    }
+}
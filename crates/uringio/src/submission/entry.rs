@@ -20,7 +20,7 @@ pub enum Ty {
 
 const BASE_SQE_SIZE: usize = size_of::<IoUringSqe>();
 
-pub trait Sqe {
+pub trait Sqe: Deref<Target = IoUringSqe> {
     const TYPE: Ty;
 
     const SETUP_FLAG: IoUringSetupFlags = match Self::TYPE {
@@ -33,9 +33,22 @@ pub trait Sqe {
         Ty::Sqe64 | Ty::SqeMix => BASE_SQE_SIZE,
         Ty::Sqe128 => BASE_SQE_SIZE * 2,
     };
+
+    /// Ring slots taken by `sqe`
+    #[inline]
+    fn slots(_sqe: &IoUringSqe) -> u32 {
+        1
+    }
 }
 
-pub trait FixSqe: Sized {}
+pub trait FixSqe: Sized {
+    /// Entry read in place as `io_uring_sqe`
+    #[inline]
+    fn as_sqe(&self) -> &IoUringSqe {
+        // SAFETY: FixSqe types share the io_uring_sqe layout, see `Op::check_size_align`
+        unsafe { &*(&raw const *self).cast() }
+    }
+}
 
 /// ## Sqe64
 #[repr(transparent)]
@@ -148,6 +161,12 @@ pub struct SqeMix {
 
 impl Sqe for SqeMix {
     const TYPE: Ty = Ty::SqeMix;
+
+    /// Kernel reads 128-byte opcodes from 2 slots
+    #[inline]
+    fn slots(sqe: &IoUringSqe) -> u32 {
+        if sqe.opcode.is_sqe128() { 2 } else { 1 }
+    }
 }
 
 impl Debug for SqeMix {
@@ -13,6 +13,8 @@ use crate::{
 };
 
 /// ## Submission Index
+/// `sq_array` indirection, maps ring position to SQE slot.
+/// Positions in `[tail, head + size)` hold the free slots, initially identity
 #[derive(Debug, Default)]
 pub struct SubmissionIndex<'fd> {
     indices: &'fd mut [u32],
@@ -48,6 +50,28 @@ impl SubmissionIndex<'_> {
             *item = idx as u32;
         }
     }
+
+    #[inline]
+    const fn mask(&self) -> usize {
+        self.indices.len() - 1
+    }
+
+    /// SQE slot at ring position `pos`
+    #[inline]
+    pub fn slot(&self, pos: u32) -> u32 {
+        self.indices[pos as usize & self.mask()]
+    }
+
+    #[inline]
+    pub fn swap(&mut self, a: u32, b: u32) {
+        let mask = self.mask();
+        self.indices.swap(a as usize & mask, b as usize & mask);
+    }
+
+    /// Ring position of `slot` in `[from, from + len)`
+    pub fn position(&self, from: u32, len: u32, slot: u32) -> Option<u32> {
+        (0..len).map(|off| from.wrapping_add(off)).find(|&pos| self.slot(pos) == slot)
+    }
 }
 
 impl Deref for SubmissionIndex<'_> {
@@ -7,8 +7,9 @@ use std::{
 
 use crate::{
     arena::Arena,
-    platform::iouring::{IoUringParams, IoUringSqFlags},
-    submission::{index::SubmissionIndex, submitter::Submitter},
+    platform::iouring::{IoUringOp, IoUringParams, IoUringSqFlags},
+    register::probe::Probe,
+    submission::{backlog::Backlog, index::SubmissionIndex, submitter::Submitter},
     uring::mode::Mode,
 };
 
@@ -22,6 +23,12 @@ pub struct SubmissionQueue<'fd, A, M, S, C> {
     pub size: u32,
     pub k_flags: &'fd AtomicU32,
     pub k_dropped: &'fd AtomicU32,
+    pub array: Option<SubmissionIndex<'fd>>,
+    pub(crate) throttled: u64,
+    pub(crate) flushes: u64,
+    pub(crate) backlog: Option<Backlog<S>>,
+    pub(crate) probe: Option<Probe>,
+    pub(crate) poisoned: bool,
 
     _marker_: PhantomData<(A, M, C)>,
 }
@@ -44,8 +51,24 @@ where
             let k_flags = sq.byte_add(sq_off.flags as _).cast().as_ref();
             let k_dropped = sq.byte_add(sq_off.dropped as _).cast().as_ref();
             SubmissionIndex::setup(sq, params);
-
-            Self { sqes, k_head, k_tail, mask, size, k_flags, k_dropped, _marker_: PhantomData }
+            let array = SubmissionIndex::new(sq, params).ok();
+
+            Self {
+                sqes,
+                k_head,
+                k_tail,
+                mask,
+                size,
+                k_flags,
+                k_dropped,
+                array,
+                throttled: 0,
+                flushes: 0,
+                backlog: None,
+                probe: None,
+                poisoned: false,
+                _marker_: PhantomData,
+            }
         }
     }
 }
@@ -68,10 +91,64 @@ impl<A, M, S, C> SubmissionQueue<'_, A, M, S, C> {
         self.flags(Ordering::Relaxed).contains(IoUringSqFlags::CQ_OVERFLOW)
     }
 
+    /// Accept pushes beyond SQ capacity, see [`Backlog`]
+    pub fn enable_backlog(&mut self) {
+        self.backlog.get_or_insert_with(Backlog::new);
+    }
+
+    /// Check pushed ops against `probe`, unsupported ops are handed back to the caller
+    pub fn set_probe(&mut self, probe: Probe) {
+        self.probe = Some(probe);
+    }
+
+    /// Whether `op` may be pushed, always true without probe
+    #[inline]
+    pub fn supports(&self, op: IoUringOp) -> bool {
+        self.probe.as_ref().is_none_or(|probe| probe.is_supported(op))
+    }
+
+    /// Entries waiting for SQ space, 0 when backlog disabled
+    #[inline]
+    pub fn backlog_len(&self) -> usize {
+        self.backlog.as_ref().map_or(0, Backlog::len)
+    }
+
+    /// Pushes rejected while CQ overflowed
+    #[inline]
+    pub const fn throttled(&self) -> u64 {
+        self.throttled
+    }
+
+    /// Overflow list flushes issued on submit
+    #[inline]
+    pub const fn flushes(&self) -> u64 {
+        self.flushes
+    }
+
+    /// Rings switched by a resize whose remap failed, see `Uring::resize`
+    #[inline]
+    pub const fn is_poisoned(&self) -> bool {
+        self.poisoned
+    }
+
     pub fn taskrun(&self) -> bool {
         self.flags(Ordering::Relaxed).contains(IoUringSqFlags::TASKRUN)
     }
 
+    #[inline]
+    pub const fn has_array(&self) -> bool {
+        self.array.is_some()
+    }
+
+    /// SQE slot at ring position `pos`
+    #[inline]
+    pub fn slot(&self, pos: u32) -> u32 {
+        match &self.array {
+            Some(array) => array.slot(pos),
+            None => pos & self.mask,
+        }
+    }
+
     #[inline]
     pub const fn get_sqe(&self, idx: u32) -> NonNull<S> {
         // SAFETY: index masked
@@ -100,7 +177,7 @@ where
     }
 
     pub fn submitter(&mut self) -> Submitter<'_, 'fd, A, M, S, C> {
-        Submitter { head: self.head(), tail: self.tail(), queue: self }
+        Submitter { head: self.head(), tail: self.tail(), reserved: 0, queue: self }
     }
 }
 
@@ -109,14 +186,13 @@ impl<A, M, S, C> Index<u32> for SubmissionQueue<'_, A, M, S, C> {
 
     #[inline]
     fn index(&self, index: u32) -> &Self::Output {
-        // TODO: handle SQARRAY SubmissionIndex
-        unsafe { self.get_sqe(index).as_ref() }
+        unsafe { self.get_sqe(self.slot(index)).as_ref() }
     }
 }
 
 impl<A, M, S, C> IndexMut<u32> for SubmissionQueue<'_, A, M, S, C> {
     #[inline]
     fn index_mut(&mut self, index: u32) -> &mut Self::Output {
-        unsafe { self.get_sqe(index).as_mut() }
+        unsafe { self.get_sqe(self.slot(index)).as_mut() }
     }
 }
@@ -1,19 +1,26 @@
+use std::{
+    mem::transmute,
+    ops::{Deref, DerefMut},
+    sync::{atomic, atomic::Ordering},
+};
+
 use crate::{
     completion::entry::{Cqe16, Cqe32, CqeMix},
     operator::{Op, noop::Nop128},
-    platform::iouring::IoUringEnterFlags,
+    platform::iouring::{IoUringEnterFlags, IoUringSqe, IoUringSqeFlags},
     shared::{
-        error::Result,
+        error::{Result, err},
         log::debug,
         null::{NULL, Null},
     },
     submission::{
-        entry::{FixSqe, Sqe64, Sqe128, SqeMix},
+        backlog::{Backlog, LINK},
+        entry::{FixSqe, Sqe, Sqe64, Sqe128, SqeMix},
         queue::SubmissionQueue,
     },
     uring::{
         enter::UringEnter,
-        mode::{Iopoll, Mode, Sqpoll},
+        mode::{Iopoll, Mode, Sqpoll, Ty},
     },
 };
 
@@ -25,25 +32,78 @@ where
 {
     pub(crate) head: u32,
     pub(crate) tail: u32,
+    pub(crate) reserved: u32,
     pub queue: &'s mut SubmissionQueue<'fd, A, M, S, C>,
 }
 
-impl<A, M, S, C> Submitter<'_, '_, A, M, S, C>
+impl<'s, 'fd, A, M, S, C> Submitter<'s, 'fd, A, M, S, C>
 where
     M: Mode,
 {
-    fn push_impl<T>(&mut self, sqe: T) -> Result<Null, T>
+    fn push_ring<T>(&mut self, sqe: T) -> Result<Null, T>
     where
-        T: Into<S> + FixSqe,
+        T: Into<S>,
     {
-        if self.is_full() {
+        if self.is_full() || self.is_throttled() {
             return Err(sqe)
         }
 
-        self.queue[self.tail] = sqe.into();
+        self.write(sqe.into());
+        Ok(NULL)
+    }
+
+    /// Write `sqe` at tail, free space checked by caller
+    fn write(&mut self, sqe: S) {
+        // Keep reserved slots behind tail
+        if let Some(array) = &mut self.queue.array
+            && self.reserved > 0
+        {
+            array.swap(self.tail, self.tail.wrapping_add(self.reserved));
+        }
+
+        self.queue[self.tail] = sqe;
         self.tail = self.tail.wrapping_add(1);
+    }
 
-        Ok(NULL)
+    /// Write a 128-byte entry as 2 halves at tail, laid out by `place`
+    fn write_wide(&mut self, lo: S, hi: S) {
+        if !self.queue.has_array() && self.tail & self.queue.mask == self.queue.mask {
+            debug!("unaligned slot, padding with Nop128");
+            let slot = self.queue.slot(self.tail);
+            let nop = Nop128::new().skip_cqe().into();
+            unsafe { self.queue.get_sqe(slot).cast::<Sqe64>().write(nop) };
+            self.tail = self.tail.wrapping_add(1);
+        }
+
+        // Kernel reads the 2 slots following the slot at tail
+        self.queue[self.tail] = lo;
+        self.queue[self.tail.wrapping_add(1)] = hi;
+        self.tail = self.tail.wrapping_add(2);
+    }
+
+    /// Lay out an entry of `slots` at `off` past tail without writing it, returns the offset
+    /// past the entry. 128-byte entries get adjacent slots by pairing, or padding without `sq_array`
+    fn place(&mut self, off: u32, slots: u32) -> Option<u32> {
+        let free = self.queue.size - self.size() - self.reserved;
+        let at = self.tail.wrapping_add(off);
+
+        let pad = if slots < 2 {
+            0
+        } else if self.reserved > 0 {
+            // Sqe128 take 2 slots, not mixed with reserved slots
+            return None;
+        } else if self.queue.has_array() {
+            // No adjacent free pair until in-flight slots are released
+            if off + 2 > free || !self.pair_slots(at) {
+                return None;
+            }
+            0
+        } else {
+            u32::from(at & self.queue.mask == self.queue.mask)
+        };
+
+        let off = off + pad + slots;
+        (off <= free).then_some(off)
     }
 
     #[inline]
@@ -61,14 +121,390 @@ where
         self.update_tail();
     }
 
+    /// Reject pushes while kernel holds overflowed CQEs, flushed on submit or `Collector::flush_overflow`
+    #[inline]
+    fn is_throttled(&self) -> bool {
+        self.queue.cq_overflow()
+    }
+
+    /// Opcode of `sqe` known to the probe, see `Uring::probe`
+    #[inline]
+    fn supports(&self, sqe: &IoUringSqe) -> bool {
+        let supported = self.queue.supports(sqe.opcode);
+        if !supported {
+            debug!("unsupported op: {:?}", sqe.opcode);
+        }
+        supported
+    }
+
+    /// Return `sqe` to the caller, counted as throttled while CQ overflowed
+    fn refuse<T, R>(&mut self, sqe: T) -> Result<R, T> {
+        if self.is_throttled() {
+            self.queue.throttled += 1;
+        }
+
+        Err(sqe)
+    }
+
+    /// Flush kernel overflow list before submitting, lifts the push throttle once CQ has space
+    fn flush_overflow(&mut self, enter: &mut UringEnter<'fd, A, M, S, C>) -> Result<Null> {
+        if self.queue.cq_overflow() {
+            debug!("flush cq overflow on submit");
+            // IORING_ENTER_GETEVENTS call io_cqring_do_overflow_flush()
+            enter.enter(0, 0, IoUringEnterFlags::GETEVENTS)?;
+            self.queue.flushes += 1;
+        }
+
+        Ok(NULL)
+    }
+
+    /// `SQ_WAKEUP` when the SQ thread of a Sqpoll ring went idle
+    pub(crate) fn wakeup_flags(&self) -> IoUringEnterFlags {
+        if !matches!(M::TYPE, Ty::Sqpoll) {
+            return IoUringEnterFlags::empty();
+        }
+
+        // TODO: void fence(SeqCst): https://github.com/axboe/liburing/issues/541
+        // Pairs with SQ thread setting NEED_WAKEUP before its last check of tail
+        atomic::fence(Ordering::SeqCst);
+
+        if self.queue.need_wakeup() {
+            IoUringEnterFlags::SQ_WAKEUP
+        } else {
+            IoUringEnterFlags::empty()
+        }
+    }
+
+    /// Move a free slot pair `(slot, slot + 1)` to positions `at` and `at + 1`,
+    /// searched in free positions from `at`
+    fn pair_slots(&mut self, at: u32) -> bool {
+        let end = self.tail.wrapping_add(self.queue.size - self.size() - self.reserved);
+        let free = end.wrapping_sub(at);
+        let last = self.queue.mask;
+        let Some(array) = &mut self.queue.array else { return false };
+
+        for off in 0..free {
+            let pos = at.wrapping_add(off);
+            let slot = array.slot(pos);
+            if slot == last {
+                continue;
+            }
+
+            if let Some(next) = array.position(at, free, slot + 1) {
+                array.swap(pos, at);
+                // Slot previously at `at` moved to `pos`
+                let next = if next == at { pos } else { next };
+                array.swap(next, at.wrapping_add(1));
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Drop unpublished entries after `tail`
+    fn rollback(&mut self, tail: u32) {
+        if let Some(array) = &mut self.queue.array
+            && self.reserved > 0
+        {
+            // Undo push_impl swaps, reserved slots back behind tail
+            let mut pos = self.tail;
+            while pos != tail {
+                pos = pos.wrapping_sub(1);
+                array.swap(pos, pos.wrapping_add(self.reserved));
+            }
+        }
+
+        self.tail = tail;
+    }
+
+    /// Push a linked chain, nothing is pushed unless committed
+    pub fn chain(&mut self) -> Chain<'_, 's, 'fd, A, M, S, C> {
+        let tail = self.tail;
+        Chain { submitter: self, tail, len: 0, staged: Vec::new() }
+    }
+
     #[inline]
     pub const fn size(&self) -> u32 {
         self.tail.wrapping_sub(self.head)
     }
 
+    #[inline]
+    pub const fn reserved(&self) -> u32 {
+        self.reserved
+    }
+
+    /// Also full while poisoned by a failed resize
     #[inline]
     pub const fn is_full(&self) -> bool {
-        self.size() == self.queue.size
+        self.queue.poisoned || self.size() + self.reserved == self.queue.size
+    }
+
+    /// Write `sqe` into a free slot without publishing it, requires `sq_array`
+    pub fn reserve<T>(&mut self, sqe: T) -> Result<SqeSlot, T>
+    where
+        T: Into<S> + FixSqe,
+    {
+        if self.is_full() || self.is_throttled() {
+            return self.refuse(sqe)
+        }
+
+        let Some(array) = &self.queue.array else { return Err(sqe) };
+
+        let slot = array.slot(self.tail.wrapping_add(self.reserved));
+        self.reserved += 1;
+        unsafe { self.queue.get_sqe(slot).write(sqe.into()) };
+
+        Ok(SqeSlot(slot))
+    }
+
+    /// Publish a reserved slot at tail, reserved slots are committed in any order
+    pub fn commit(&mut self, slot: SqeSlot) -> Result<Null> {
+        let Some(array) = &mut self.queue.array else {
+            return err!("IoUring setup with IORING_SETUP_NO_SQARRAY flag");
+        };
+
+        let Some(pos) = array.position(self.tail, self.reserved, slot.0) else {
+            return err!("Sqe slot {} not reserved by this submitter", slot.0);
+        };
+
+        array.swap(pos, self.tail);
+        self.tail = self.tail.wrapping_add(1);
+        self.reserved -= 1;
+
+        Ok(NULL)
+    }
+}
+
+impl<A, M, S, C> Submitter<'_, '_, A, M, S, C>
+where
+    M: Mode,
+    S: Sqe,
+{
+    fn push_impl<T>(&mut self, sqe: T) -> Result<Null, T>
+    where
+        T: Into<S> + FixSqe,
+    {
+        if !self.supports(sqe.as_sqe()) {
+            return Err(sqe)
+        }
+
+        let Some(backlog) = &self.queue.backlog else {
+            return match self.push_ring(sqe) {
+                Err(sqe) => self.refuse(sqe),
+                Ok(null) => Ok(null),
+            };
+        };
+
+        // A chain longer than SQ never enters the ring
+        if !backlog.admits(sqe.as_sqe(), self.queue.size) {
+            return Err(sqe)
+        }
+
+        // FIFO behind backlog, link chains only enter the ring as a whole
+        let sqe = if backlog.is_empty() && !sqe.as_sqe().flags.intersects(LINK) {
+            match self.push_ring(sqe) {
+                Ok(null) => return Ok(null),
+                Err(sqe) => sqe,
+            }
+        } else {
+            sqe
+        };
+
+        if let Some(backlog) = &mut self.queue.backlog {
+            backlog.push(sqe.into(), None);
+        }
+        Ok(NULL)
+    }
+
+    /// Push a 128-byte entry as 2 halves, FIFO behind backlog
+    fn push_wide(&mut self, lo: S, hi: S) -> Result<Null, (S, S)> {
+        // Kernel takes the width from opcode
+        if S::slots(&lo) < 2 || !self.supports(&lo) {
+            return Err((lo, hi))
+        }
+
+        let backlog = self.queue.backlog.as_ref();
+        if backlog.is_none_or(|backlog| backlog.is_empty() && !lo.flags.intersects(LINK)) {
+            if !self.is_full() && !self.is_throttled() && self.place(0, 2).is_some() {
+                self.write_wide(lo, hi);
+                return Ok(NULL)
+            }
+
+            if self.queue.backlog.is_none() {
+                return self.refuse((lo, hi))
+            }
+        }
+
+        match &mut self.queue.backlog {
+            // A chain longer than SQ never enters the ring
+            Some(backlog) if backlog.admits(&lo, self.queue.size) => {
+                backlog.push(lo, Some(hi));
+                Ok(NULL)
+            },
+            _ => Err((lo, hi)),
+        }
+    }
+
+    /// Move whole link chains from backlog into free SQ space, returns entries moved
+    pub fn drain_backlog(&mut self) -> u32 {
+        let mut moved = 0;
+
+        while !self.queue.cq_overflow()
+            && !self.queue.poisoned
+            && let Some(len) = self.queue.backlog.as_ref().and_then(Backlog::front_chain)
+        {
+            // Chains are admitted within SQ size, lay out the whole chain before writing
+            let (mut idx, mut off) = (0, Some(0));
+            while idx < len
+                && let Some(at) = off
+                && let Some(sqe) = self.queue.backlog.as_ref().and_then(|b| b.get(idx))
+            {
+                let slots = S::slots(sqe);
+                idx += slots as usize;
+                off = self.place(at, slots);
+            }
+            if off.is_none() {
+                break;
+            }
+
+            let mut idx = 0;
+            while idx < len
+                && let Some(backlog) = &mut self.queue.backlog
+                && let Some(sqe) = backlog.pop()
+            {
+                if S::slots(&sqe) > 1
+                    && let Some(hi) = backlog.pop()
+                {
+                    self.write_wide(sqe, hi);
+                    (idx, moved) = (idx + 2, moved + 2);
+                } else {
+                    self.write(sqe);
+                    (idx, moved) = (idx + 1, moved + 1);
+                }
+            }
+        }
+
+        moved
+    }
+
+    /// Update head, drain backlog and publish tail
+    pub fn sync(&mut self) {
+        self.update_head();
+        self.drain_backlog();
+        self.update_tail();
+    }
+
+    /// Entries waiting for SQ space
+    #[inline]
+    pub fn backlog_len(&self) -> usize {
+        self.queue.backlog_len()
+    }
+}
+
+/// ## Chain
+/// Linked SQEs pushed all or nothing, rolled back on drop unless committed.
+/// Staged and committed behind the backlog when enabled
+#[derive(Debug)]
+pub struct Chain<'c, 's, 'fd, A, M, S, C>
+where
+    M: Mode,
+{
+    submitter: &'c mut Submitter<'s, 'fd, A, M, S, C>,
+    tail: u32,
+    len: u32,
+    staged: Vec<S>,
+}
+
+impl<A, M, S, C> Chain<'_, '_, '_, A, M, S, C>
+where
+    M: Mode,
+    S: Sqe + DerefMut<Target = IoUringSqe>,
+{
+    #[inline]
+    pub const fn len(&self) -> u32 {
+        self.len
+    }
+
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Link `sqe` after previous entries, keeps `IO_HARDLINK`
+    pub fn push<T>(&mut self, sqe: T) -> Result<Null, T>
+    where
+        T: Into<S> + FixSqe,
+    {
+        if !self.submitter.supports(sqe.as_sqe()) {
+            return Err(sqe)
+        }
+
+        if let Some(backlog) = &self.submitter.queue.backlog {
+            // Terminated by commit, a chain longer than SQ never enters the ring
+            let sqes = self.staged.iter().map(Deref::deref).chain([sqe.as_sqe()]);
+            if backlog.chain_slots(sqes) > self.submitter.queue.size {
+                return Err(sqe)
+            }
+
+            let mut sqe = sqe.into();
+            sqe.flags |= IoUringSqeFlags::IO_LINK;
+            self.staged.push(sqe);
+            self.len += 1;
+            return Ok(NULL)
+        }
+
+        if let Err(sqe) = self.submitter.push_ring(sqe) {
+            return self.submitter.refuse(sqe)
+        }
+
+        let last = self.submitter.tail.wrapping_sub(1);
+        self.submitter.queue[last].flags |= IoUringSqeFlags::IO_LINK;
+        self.len += 1;
+
+        Ok(NULL)
+    }
+
+    /// Terminate the chain at the last entry and keep it in the queue
+    pub fn commit(mut self) -> u32 {
+        if let Some(last) = self.staged.last_mut() {
+            last.flags.remove(LINK);
+            if let Some(backlog) = &mut self.submitter.queue.backlog {
+                self.staged.drain(..).for_each(|sqe| backlog.push(sqe, None));
+            }
+        } else if self.len > 0 {
+            let last = self.submitter.tail.wrapping_sub(1);
+            self.submitter.queue[last].flags.remove(LINK);
+        }
+
+        // Committed, nothing to roll back
+        self.tail = self.submitter.tail;
+        self.len
+    }
+}
+
+impl<A, M, S, C> Drop for Chain<'_, '_, '_, A, M, S, C>
+where
+    M: Mode,
+{
+    fn drop(&mut self) {
+        if self.tail != self.submitter.tail || !self.staged.is_empty() {
+            debug!("rollback chain: {} entries", self.len);
+            self.submitter.rollback(self.tail);
+        }
+    }
+}
+
+/// ## Sqe Slot
+/// SQE slot reserved by [`Submitter::reserve`], released when the submitter drops
+#[derive(Debug)]
+#[must_use]
+pub struct SqeSlot(u32);
+
+impl SqeSlot {
+    #[inline]
+    pub const fn index(&self) -> u32 {
+        self.0
     }
 }
 
@@ -131,25 +567,12 @@ where
     M: Mode,
 {
     fn push(&mut self, sqe: Sqe128) -> Result<Null, Sqe128> {
-        // Sqe128 take 2 slots
-        if self.size() + 2 > self.queue.size {
-            return Err(sqe)
-        }
-
-        // Padding with IORING_OP_NOP128
-        if self.tail.wrapping_add(1) & self.queue.mask == 0 {
-            if self.size() + 3 > self.queue.size {
-                return Err(sqe)
-            }
-            debug!("unaligned slot, padding with Nop128");
-            // Nop128 slot checked
-            let _ = self.push(Nop128::new().skip_cqe());
-        }
-
-        unsafe { self.queue.get_sqe(self.tail).cast::<Sqe128>().write(sqe) };
-        self.tail = self.tail.wrapping_add(2);
-
-        Ok(NULL)
+        // SAFETY: Sqe128 is 2 consecutive io_uring_sqe, SqeMix is transparent over one
+        let [lo, hi] = unsafe { transmute::<Sqe128, [SqeMix; 2]>(sqe) };
+        self.push_wide(lo, hi).map_err(|(lo, hi)| {
+            // SAFETY: rejoin the halves split above
+            unsafe { transmute([lo, hi]) }
+        })
     }
 }
 
@@ -157,6 +580,7 @@ where
 impl<T, A, M, S, C> Submit<T> for Submitter<'_, '_, A, M, S, C>
 where
     M: Mode,
+    S: Sqe,
     T: Op + Into<S>,
 {
     fn push(&mut self, op: T) -> Result<Null, T> {
@@ -164,20 +588,391 @@ where
     }
 }
 
-impl<'fd, A, S, C> Submitter<'_, 'fd, A, Iopoll, S, C> {
+impl<'fd, A, S, C> Submitter<'_, 'fd, A, Iopoll, S, C>
+where
+    S: Sqe,
+{
     pub fn submit(
         &mut self,
         enter: &mut UringEnter<'fd, A, Iopoll, S, C>,
         min_complete: u32,
     ) -> Result<u32> {
-        self.update();
+        self.flush_overflow(enter)?;
+        self.sync();
 
         enter.enter(self.size(), min_complete, IoUringEnterFlags::GETEVENTS)
     }
 }
 
-impl<A, S, C> Submitter<'_, '_, A, Sqpoll, S, C> {
-    pub fn submit(&mut self) {
-        self.update();
+impl<'fd, A, S, C> Submitter<'_, 'fd, A, Sqpoll, S, C>
+where
+    S: Sqe,
+{
+    /// Publish tail and wake up the idle SQ thread, returns entries not yet consumed
+    pub fn submit(&mut self, enter: &mut UringEnter<'fd, A, Sqpoll, S, C>) -> Result<u32> {
+        self.flush_overflow(enter)?;
+        self.sync();
+
+        let flags = self.wakeup_flags();
+        if flags.is_empty() {
+            return Ok(self.size());
+        }
+
+        debug!("wakeup sq thread");
+        enter.enter(self.size(), 0, flags)
+    }
+
+    /// Wait for the SQ thread to free a slot when SQ is full
+    pub fn sq_wait(&mut self, enter: &mut UringEnter<'fd, A, Sqpoll, S, C>) -> Result<Null> {
+        self.sync();
+
+        if self.is_full() {
+            let flags = self.wakeup_flags() | IoUringEnterFlags::SQ_WAIT;
+            enter.enter(0, 0, flags)?;
+            self.update_head();
+        }
+
+        Ok(NULL)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{thread, time::Duration};
+
+    use super::*;
+    use crate::{
+        arena::Arena,
+        completion::{collector::Collector, entry::Cqe16},
+        operator::noop::Nop,
+        platform::iouring::Errno,
+        shared::macros::test_uring,
+        uring::UringIo,
+    };
+
+    #[test]
+    fn test_sqarray_out_of_order() {
+        let (fd, args, arena) = Iopoll::new::<Sqe64, Cqe16>(4).sqarray().setup().unwrap();
+        let mut uring = UringIo::new(&fd, &args, arena).unwrap();
+
+        let array = unsafe { uring.arena().sq().byte_add(args.sq_off.array as _) };
+        assert_eq!(
+            uring.sq.array.as_deref().map(<[u32]>::as_ptr),
+            Some(array.cast::<u32>().as_ptr().cast_const())
+        );
+
+        let (enter, mut submitter, mut collector) = uring.borrow();
+        let a = submitter.reserve(Nop::new().user_data(1)).unwrap();
+        let b = submitter.reserve(Nop::new().user_data(2)).unwrap();
+        let c = submitter.reserve(Nop::new().user_data(3)).unwrap();
+        submitter.push(Nop::new().user_data(4)).unwrap();
+        assert!(submitter.is_full());
+        assert!(submitter.push(Nop::new()).is_err());
+
+        submitter.commit(c).unwrap();
+        submitter.commit(a).unwrap();
+        submitter.commit(b).unwrap();
+        assert_eq!(submitter.reserved(), 0);
+        assert_eq!(submitter.submit(enter, 4).unwrap(), 4);
+
+        collector.update_tail();
+        let order: Vec<_> = collector.by_ref().map(|cqe| cqe.user_data.u64_()).collect();
+        assert_eq!(order, [4, 3, 1, 2]);
+        collector.update_head();
+
+        // Freed slots are reused in the permuted order
+        submitter.update_head();
+        for user_data in 5..9 {
+            submitter.push(Nop::new().user_data(user_data)).unwrap();
+        }
+        assert_eq!(submitter.submit(enter, 4).unwrap(), 4);
+
+        collector.update_tail();
+        let order: Vec<_> = collector.map(|cqe| cqe.user_data.u64_()).collect();
+        assert_eq!(order, [5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn test_sqarray_pair_slots() {
+        // NOTE: pairing only depends on the SQ array, SQE_MIXED needs a newer kernel
+        test_uring!(uring = Iopoll::new::<Sqe64, Cqe16>(4).sqarray());
+
+        let (enter, mut submitter, mut collector) = uring.borrow();
+        let array = submitter.queue.array.as_mut().unwrap();
+        array.swap(0, 1);
+        array.swap(2, 3);
+        assert_eq!(**array, [1, 0, 3, 2]);
+
+        assert!(submitter.pair_slots(submitter.tail));
+        assert_eq!((submitter.queue.slot(0), submitter.queue.slot(1)), (1, 2));
+        submitter.push(Nop::new().user_data(1)).unwrap();
+        submitter.push(Nop::new().user_data(2)).unwrap();
+
+        // Slots 0 and 3 left, not adjacent
+        assert!(!submitter.pair_slots(submitter.tail));
+        assert_eq!(submitter.submit(enter, 2).unwrap(), 2);
+        collector.update();
+        assert_eq!(collector.by_ref().count(), 2);
+        collector.update_head();
+
+        submitter.update_head();
+        assert!(submitter.pair_slots(submitter.tail));
+        let pair = (submitter.queue.slot(2), submitter.queue.slot(3));
+        assert_eq!(pair.0 + 1, pair.1);
+    }
+
+    #[test]
+    fn test_place_padding() {
+        test_uring!(uring = Iopoll::new::<Sqe64, Cqe16>(4));
+
+        let (enter, mut submitter, mut collector) = uring.borrow();
+        for _ in 0..3 {
+            submitter.push(Nop::new()).unwrap();
+        }
+        assert_eq!(submitter.place(0, 2), None);
+        assert_eq!(submitter.submit(enter, 3).unwrap(), 3);
+        collector.update();
+        assert_eq!(collector.by_ref().count(), 3);
+
+        // 128-byte entry at the last slot is padded past the wrap
+        submitter.update_head();
+        assert_eq!(submitter.place(0, 2), Some(3));
+        assert_eq!(submitter.place(0, 1), Some(1));
+        assert_eq!(submitter.place(1, 2), Some(3));
+        assert_eq!(submitter.place(2, 2), Some(4));
+        assert_eq!(submitter.place(3, 2), None);
+    }
+
+    #[test]
+    fn test_chain() {
+        test_uring!(uring = Iopoll::new::<Sqe64, Cqe16>(4));
+
+        let (enter, mut submitter, mut collector) = uring.borrow();
+        submitter.push(Nop::new().user_data(1)).unwrap();
+
+        // Too long, rolled back on drop
+        let mut chain = submitter.chain();
+        for user_data in 2..5 {
+            chain.push(Nop::new().user_data(user_data)).unwrap();
+        }
+        assert!(chain.push(Nop::new()).is_err());
+        drop(chain);
+        assert_eq!(submitter.size(), 1);
+
+        let mut chain = submitter.chain();
+        chain.push(Nop::new().user_data(2)).unwrap();
+        chain.push(Nop::new().inject_errno(Errno::AGAIN).user_data(3)).unwrap();
+        chain.push(Nop::new().user_data(4)).unwrap();
+        assert_eq!(chain.commit(), 3);
+        assert!(!submitter.queue[3].flags.contains(IoUringSqeFlags::IO_LINK));
+        assert_eq!(submitter.submit(enter, 4).unwrap(), 4);
+
+        // Linked entries are issued from deferred task work, release CQ space to run it
+        let mut results = Vec::new();
+        while results.len() < 4 {
+            submitter.submit(enter, 0).unwrap();
+            collector.update();
+            results.extend(collector.by_ref().map(|cqe| (cqe.user_data.u64_(), cqe.res)));
+        }
+        let (again, canceled) = (-Errno::AGAIN.raw_os_error(), -Errno::CANCELED.raw_os_error());
+        assert_eq!(results, [(1, 0), (2, 0), (3, again), (4, canceled)]);
+    }
+
+    #[test]
+    fn test_chain_rollback_reserved() {
+        test_uring!(uring = Iopoll::new::<Sqe64, Cqe16>(4).sqarray());
+
+        let (enter, mut submitter, mut collector) = uring.borrow();
+        let slot = submitter.reserve(Nop::new().user_data(1)).unwrap();
+
+        let mut chain = submitter.chain();
+        chain.push(Nop::new().user_data(2)).unwrap();
+        chain.push(Nop::new().user_data(3)).unwrap();
+        drop(chain);
+
+        submitter.commit(slot).unwrap();
+        assert_eq!(submitter.submit(enter, 1).unwrap(), 1);
+
+        collector.update_tail();
+        assert_eq!(collector.next().map(|cqe| cqe.user_data.u64_()), Some(1));
+    }
+
+    fn wait_cqes<A, S, C>(collector: &mut Collector<'_, '_, A, Sqpoll, S, C>, nr: u32) -> bool {
+        for _ in 0..1000 {
+            collector.update_tail();
+            if collector.size() >= nr {
+                return true;
+            }
+            thread::sleep(Duration::from_millis(1));
+        }
+        false
+    }
+
+    #[test]
+    fn test_sqpoll_wakeup() {
+        test_uring!(uring = Sqpoll::new::<Sqe64, Cqe16>(8).sqpoll(10));
+
+        let (enter, mut submitter, mut collector) = uring.borrow();
+        submitter.push(Nop::new().user_data(1)).unwrap();
+        submitter.submit(enter).unwrap();
+        assert!(wait_cqes(&mut collector, 1));
+        assert_eq!(collector.next().map(|cqe| cqe.user_data.u64_()), Some(1));
+        collector.update_head();
+
+        // SQ thread goes idle
+        thread::sleep(Duration::from_millis(100));
+        assert!(submitter.queue.need_wakeup());
+
+        submitter.push(Nop::new().user_data(2)).unwrap();
+        submitter.submit(enter).unwrap();
+        assert!(wait_cqes(&mut collector, 1));
+        assert_eq!(collector.next().map(|cqe| cqe.user_data.u64_()), Some(2));
+    }
+
+    #[test]
+    fn test_sq_wait() {
+        test_uring!(uring = Sqpoll::new::<Sqe64, Cqe16>(8).sqpoll(10));
+
+        let (enter, mut submitter, mut collector) = uring.borrow();
+        thread::sleep(Duration::from_millis(100));
+
+        while submitter.push(Nop::new()).is_ok() {}
+        assert!(submitter.is_full());
+
+        submitter.sq_wait(enter).unwrap();
+        assert!(!submitter.is_full());
+        assert!(wait_cqes(&mut collector, 1));
+    }
+
+    #[test]
+    fn test_submit_flush_overflow() {
+        test_uring!(uring = Sqpoll::new::<Sqe64, Cqe16>(2).cqsize(2));
+
+        let (enter, mut submitter, mut collector) = uring.borrow();
+        for user_data in [[1, 2], [3, 4]] {
+            for user_data in user_data {
+                submitter.push(Nop::new().user_data(user_data)).unwrap();
+            }
+            submitter.submit(enter).unwrap();
+            while submitter.size() > 0 {
+                thread::sleep(Duration::from_millis(1));
+                submitter.update_head();
+            }
+        }
+        while !submitter.queue.cq_overflow() {
+            thread::sleep(Duration::from_millis(1));
+        }
+        collector.update_tail();
+        assert!(submitter.push(Nop::new()).is_err());
+
+        let mut user_datas: Vec<_> = collector.by_ref().map(|cqe| cqe.user_data.u64_()).collect();
+        collector.update_head();
+
+        // Released CQ space takes the overflow list, throttle lifted
+        submitter.submit(enter).unwrap();
+        assert!(!submitter.queue.cq_overflow());
+        collector.update_tail();
+        user_datas.extend(collector.by_ref().map(|cqe| cqe.user_data.u64_()));
+        collector.update_head();
+
+        submitter.push(Nop::new().user_data(5)).unwrap();
+        submitter.submit(enter).unwrap();
+        assert!(wait_cqes(&mut collector, 1));
+        user_datas.extend(collector.by_ref().map(|cqe| cqe.user_data.u64_()));
+        assert_eq!(user_datas, [1, 2, 3, 4, 5]);
+        drop((submitter, collector));
+
+        let stats = uring.overflow_stats();
+        assert_eq!((stats.flushes, stats.throttled), (1, 1));
+    }
+
+    #[test]
+    fn test_backlog() {
+        test_uring!(uring = Iopoll::new::<Sqe64, Cqe16>(4));
+        let mut uring = uring.backlog();
+
+        let (enter, mut submitter, mut collector) = uring.borrow();
+        for user_data in 1..7 {
+            submitter.push(Nop::new().user_data(user_data)).unwrap();
+        }
+        submitter.push(Nop::new().user_data(7).io_link()).unwrap();
+        submitter.push(Nop::new().user_data(8).io_link()).unwrap();
+        submitter.push(Nop::new().user_data(9)).unwrap();
+        assert_eq!((submitter.size(), submitter.backlog_len()), (4, 5));
+
+        // Chain 7..=9 waits for 3 free slots
+        let mut user_datas = Vec::new();
+        for (submitted, backlog_len) in [(4, 5), (2, 3), (3, 0)] {
+            assert_eq!(submitter.submit(enter, 0).unwrap(), submitted);
+            assert_eq!(submitter.backlog_len(), backlog_len);
+            collector.update();
+            user_datas.extend(collector.by_ref().map(|cqe| cqe.user_data.u64_()));
+        }
+
+        // Linked entries are issued from deferred task work
+        while user_datas.len() < 9 {
+            submitter.submit(enter, 0).unwrap();
+            collector.update();
+            user_datas.extend(collector.by_ref().map(|cqe| cqe.user_data.u64_()));
+        }
+        assert_eq!(user_datas, (1..10).collect::<Vec<_>>());
+
+        // Unterminated chain stays in backlog
+        submitter.push(Nop::new().user_data(10).io_link()).unwrap();
+        assert_eq!(submitter.submit(enter, 0).unwrap(), 0);
+        assert_eq!(submitter.backlog_len(), 1);
+    }
+
+    #[test]
+    fn test_backlog_chain() {
+        test_uring!(uring = Iopoll::new::<Sqe64, Cqe16>(4));
+        let mut uring = uring.backlog();
+
+        let (enter, mut submitter, mut collector) = uring.borrow();
+        for user_data in 1..6 {
+            submitter.push(Nop::new().user_data(user_data)).unwrap();
+        }
+
+        // Chain staged behind backlog, longer than SQ rejected
+        let mut chain = submitter.chain();
+        for user_data in 6..10 {
+            chain.push(Nop::new().user_data(user_data)).unwrap();
+        }
+        assert!(chain.push(Nop::new()).is_err());
+        drop(chain);
+        assert_eq!(submitter.backlog_len(), 1);
+
+        let mut chain = submitter.chain();
+        chain.push(Nop::new().user_data(6)).unwrap();
+        chain.push(Nop::new().user_data(7)).unwrap();
+        assert_eq!(chain.commit(), 2);
+        assert_eq!(submitter.backlog_len(), 3);
+
+        // Linked entry leaves a slot for the last one
+        for user_data in 8..11 {
+            submitter.push(Nop::new().user_data(user_data).io_link()).unwrap();
+        }
+        assert!(submitter.push(Nop::new().io_link()).is_err());
+        submitter.push(Nop::new().user_data(11)).unwrap();
+        assert_eq!(submitter.backlog_len(), 7);
+
+        let mut user_datas = Vec::new();
+        while user_datas.len() < 11 {
+            submitter.submit(enter, 0).unwrap();
+            collector.update();
+            user_datas.extend(collector.by_ref().map(|cqe| cqe.user_data.u64_()));
+        }
+        // Linked entries of both chains are issued from deferred task work in any order
+        user_datas[5..].sort_unstable();
+        assert_eq!(user_datas, (1..12).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_reserve_no_sqarray() {
+        test_uring!(uring = Iopoll::new::<Sqe64, Cqe16>(4));
+
+        let mut submitter = uring.submitter();
+        assert!(submitter.reserve(Nop::new()).is_err());
+        assert!(submitter.commit(SqeSlot(0)).is_err());
     }
 }
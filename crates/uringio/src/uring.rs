@@ -2,13 +2,16 @@ pub mod args;
 pub mod enter;
 pub mod feat;
 pub mod mode;
+pub mod wait;
+
+use std::time::Duration;
 
 use crate::{
     arena::Arena,
     completion::{
         collector::Collector,
         entry::{Cqe, Cqe16, Cqe32, CqeMix},
-        queue::CompletionQueue,
+        queue::{CompletionQueue, OverflowStats},
     },
     platform::iouring::OwnedFd,
     shared::error::Result,
@@ -17,7 +20,12 @@ use crate::{
         queue::SubmissionQueue,
         submitter::Submitter,
     },
-    uring::{args::UringArgs, enter::UringEnter, mode::Mode},
+    uring::{
+        args::UringArgs,
+        enter::UringEnter,
+        mode::Mode,
+        wait::{WaitArg, WaitStatus},
+    },
 };
 
 /// ## Uring
@@ -26,7 +34,7 @@ pub struct Uring<'fd, A, M, S, C> {
     pub enter: UringEnter<'fd, A, M, S, C>,
     pub sq: SubmissionQueue<'fd, A, M, S, C>,
     pub cq: CompletionQueue<'fd, A, M, S, C>,
-    arena: A,
+    pub(crate) arena: A,
 }
 
 impl<'fd, A, M, S, C> Uring<'fd, A, M, S, C>
@@ -61,6 +69,27 @@ where
         Ok(self)
     }
 
+    pub fn overflow_stats(&self) -> OverflowStats {
+        OverflowStats {
+            dropped: self.cq.overflow(),
+            flushes: self.cq.flushes() + self.sq.flushes(),
+            throttled: self.sq.throttled(),
+        }
+    }
+
+    /// Hand back pushed ops the kernel does not support, so callers fall back to another op
+    pub fn probe(mut self) -> Result<Self> {
+        let probe = self.enter.probe()?;
+        self.sq.set_probe(probe);
+        Ok(self)
+    }
+
+    /// Accept pushes beyond SQ capacity, drained on submit
+    pub fn backlog(mut self) -> Self {
+        self.sq.enable_backlog();
+        self
+    }
+
     pub fn arena(&self) -> &A {
         &self.arena
     }
@@ -73,6 +102,26 @@ where
         self.cq.collector()
     }
 
+    /// Submit and wait for `min_complete` completions up to `timeout`
+    pub fn submit_and_wait_timeout(
+        &mut self,
+        min_complete: u32,
+        timeout: Duration,
+    ) -> Result<WaitStatus> {
+        self.submit_and_wait_arg(min_complete, &WaitArg::new().timeout(timeout))
+    }
+
+    pub fn submit_and_wait_arg(
+        &mut self,
+        min_complete: u32,
+        arg: &WaitArg<'_>,
+    ) -> Result<WaitStatus> {
+        let (enter, mut submitter, mut collector) = self.borrow();
+        submitter.sync();
+        let flags = submitter.wakeup_flags();
+        collector.wait_impl(enter, submitter.size(), min_complete, flags, arg)
+    }
+
     pub fn borrow(
         &mut self,
     ) -> (
@@ -128,6 +128,12 @@ where
         self
     }
 
+    /// Keep the `sq_array` indirection, allows out of order submission
+    pub fn sqarray(mut self) -> Self {
+        self.params.flags.remove(IoUringSetupFlags::NO_SQARRAY);
+        self
+    }
+
     // Must use with IOPOLL
     pub fn hybrid_iopoll(mut self) -> Self {
         debug_assert!(self.flags.contains(IoUringSetupFlags::IOPOLL));
@@ -226,6 +232,12 @@ where
     }
 }
 
+impl<A, M, S, C> From<IoUringParams> for UringArgs<A, M, S, C> {
+    fn from(params: IoUringParams) -> Self {
+        UringArgs { params, _marker_: PhantomData }
+    }
+}
+
 impl<A, M, S, C> Deref for UringArgs<A, M, S, C> {
     type Target = IoUringParams;
 
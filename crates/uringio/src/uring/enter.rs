@@ -2,17 +2,22 @@ use std::{io::Result, marker::PhantomData};
 
 use crate::{
     platform::iouring::{
-        AsFd, BorrowedFd, IoUringEnterFlags, IoUringFeatureFlags, OwnedFd, io_uring_enter,
+        AsFd, BorrowedFd, ClockId, IoUringEnterFlags, IoUringFeatureFlags, IoUringRegisterFlags,
+        IoUringRegisterOp, IoUringSetupFlags, OwnedFd, c_void, io_uring_enter,
+        io_uring_register_with,
     },
     uring::{args::UringArgs, mode::Mode},
 };
 
 #[derive(Debug)]
 pub struct UringEnter<'fd, A, M, S, C> {
+    pub(crate) ring_fd: BorrowedFd<'fd>,
     pub(crate) enter_fd: BorrowedFd<'fd>,
     // TODO: init flags
     pub(crate) enter_flags: IoUringEnterFlags,
     pub(crate) features: IoUringFeatureFlags,
+    pub(crate) setup_flags: IoUringSetupFlags,
+    pub(crate) clock: ClockId,
 
     _marker_: PhantomData<(A, M, S, C)>,
 }
@@ -23,20 +28,34 @@ where
 {
     pub fn new(fd: &'fd OwnedFd, args: &UringArgs<A, M, S, C>) -> Self {
         Self {
+            ring_fd: fd.as_fd(),
             enter_fd: fd.as_fd(),
             enter_flags: M::ENTER_FLAG,
             features: args.features,
+            setup_flags: args.flags,
+            clock: ClockId::Monotonic,
             _marker_: PhantomData,
         }
     }
 }
 
-impl<A, M, S, C> UringEnter<'_, A, M, S, C> {
+impl<'fd, A, M, S, C> UringEnter<'fd, A, M, S, C> {
+    /// Real ring fd, stays valid after ring fd registration
+    #[inline]
+    pub fn ring_fd(&self) -> BorrowedFd<'fd> {
+        self.ring_fd
+    }
+
     #[inline]
     pub fn features(&self) -> &IoUringFeatureFlags {
         &self.features
     }
 
+    #[inline]
+    pub fn setup_flags(&self) -> &IoUringSetupFlags {
+        &self.setup_flags
+    }
+
     pub fn set_iowait(&mut self, enable: bool) {
         #[cfg(feature = "features-checker")]
         {
@@ -63,6 +82,23 @@ impl<A, M, S, C> UringEnter<'_, A, M, S, C> {
             io_uring_enter(self.enter_fd, to_submit, min_complete, self.enter_flags | flags)?
         })
     }
+
+    /// Unsafe: `arg` must match `op` and stay valid for `nr_args` entries
+    #[inline]
+    pub unsafe fn register(
+        &self,
+        op: IoUringRegisterOp,
+        arg: *const c_void,
+        nr_args: u32,
+    ) -> Result<u32> {
+        let flags = if self.is_ring_registered() {
+            IoUringRegisterFlags::USE_REGISTERED_RING
+        } else {
+            IoUringRegisterFlags::empty()
+        };
+
+        Ok(unsafe { io_uring_register_with(self.enter_fd, op, flags, arg, nr_args)? })
+    }
 }
 
 impl<A, M, S, C> Drop for UringEnter<'_, A, M, S, C> {
@@ -6,6 +6,7 @@ use crate::{
     },
 };
 
+/// Features the ring layout depends on, unsupported ops fall back through `Uring::probe`
 pub fn check_setup_features(features: IoUringFeatureFlags) -> Result<Null> {
     if !features.contains(IoUringFeatureFlags::SINGLE_MMAP) {
         return err!("Feature IORING_FEAT_SINGLE_MMAP Not Supported");
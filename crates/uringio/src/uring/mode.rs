@@ -35,6 +35,8 @@ pub trait Mode: Sized {
 }
 
 /// ## Iopoll
+/// `IORING_SETUP_IOPOLL` ring, ops without IOPOLL support are rejected with `EINVAL`,
+/// e.g. epoll, pipe, fixed fd install and multishot read
 #[derive(Debug)]
 pub struct Iopoll;
 
@@ -0,0 +1,151 @@
+use std::time::Duration;
+
+use crate::{
+    platform::iouring::{
+        Errno, IoUringEnterFlags, IoUringFeatureFlags, IoUringGeteventsArg, IoUringPtr,
+        KernelSigSet, Timespec, io_uring_enter_arg,
+    },
+    shared::error::{Result, err},
+    uring::enter::UringEnter,
+};
+
+/// ## Wait Status
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum WaitStatus {
+    /// `min_complete` completions ready
+    Ready,
+
+    /// Timeout expired before `min_complete` completions
+    TimedOut,
+}
+
+/// ## Wait Arg
+/// Extended wait argument of `IORING_ENTER_EXT_ARG`
+#[derive(Debug, Default, Copy, Clone)]
+pub struct WaitArg<'a> {
+    pub(crate) ts: Option<Timespec>,
+    pub(crate) abs: bool,
+    pub(crate) sigmask: Option<&'a KernelSigSet>,
+    pub(crate) min_wait_usec: u32,
+}
+
+impl<'a> WaitArg<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Relative timeout, saturated on overflow
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.ts = Some(Timespec {
+            tv_sec: timeout.as_secs().try_into().unwrap_or(i64::MAX),
+            tv_nsec: timeout.subsec_nanos().into(),
+        });
+        self.abs = false;
+        self
+    }
+
+    /// Absolute timeout on the ring clock, see `UringEnter::deadline`
+    pub fn deadline(mut self, deadline: Timespec) -> Self {
+        self.ts = Some(deadline);
+        self.abs = true;
+        self
+    }
+
+    pub fn sigmask(mut self, sigmask: &'a KernelSigSet) -> Self {
+        self.sigmask = Some(sigmask);
+        self
+    }
+
+    /// Wait for `min_complete` at most `min_wait`, then return with any completion.
+    /// Requires `IORING_FEAT_MIN_TIMEOUT`
+    pub fn min_wait(mut self, min_wait: Duration) -> Self {
+        self.min_wait_usec = min_wait.as_micros().try_into().unwrap_or(u32::MAX);
+        self
+    }
+
+    /// Completions needed for [`WaitStatus::Ready`]
+    #[inline]
+    pub(crate) const fn ready_count(&self, min_complete: u32) -> u32 {
+        if self.min_wait_usec > 0 && min_complete > 1 { 1 } else { min_complete }
+    }
+
+    #[inline]
+    pub(crate) fn enter_flags(&self) -> IoUringEnterFlags {
+        if self.abs { IoUringEnterFlags::ABS_TIMER } else { IoUringEnterFlags::empty() }
+    }
+}
+
+impl<A, M, S, C> UringEnter<'_, A, M, S, C> {
+    pub fn enter_arg(
+        &self,
+        to_submit: u32,
+        min_complete: u32,
+        flags: IoUringEnterFlags,
+        arg: &WaitArg<'_>,
+    ) -> Result<u32> {
+        // NOTE: kernel without IORING_FEAT_MIN_TIMEOUT ignores min_wait_usec
+        if arg.min_wait_usec > 0 && !self.features.contains(IoUringFeatureFlags::MIN_TIMEOUT) {
+            return err!("Feature IORING_FEAT_MIN_TIMEOUT Not Supported");
+        }
+
+        let mut getevents = IoUringGeteventsArg::default();
+        if let Some(ts) = &arg.ts {
+            getevents.ts = IoUringPtr::new((&raw const *ts).cast_mut().cast());
+        }
+        if let Some(sigmask) = arg.sigmask {
+            getevents.sigmask = IoUringPtr::new((&raw const *sigmask).cast_mut().cast());
+            getevents.sigmask_sz = size_of::<KernelSigSet>() as _;
+        }
+        getevents.min_wait_usec = arg.min_wait_usec;
+
+        let flags = self.enter_flags | flags | arg.enter_flags() | IoUringEnterFlags::EXT_ARG;
+        Ok(unsafe {
+            io_uring_enter_arg(self.enter_fd, to_submit, min_complete, flags, Some(&getevents))?
+        })
+    }
+
+    /// `enter_arg` with `GETEVENTS`, `ETIME` reported as [`WaitStatus::TimedOut`]
+    pub fn enter_wait(
+        &self,
+        to_submit: u32,
+        min_complete: u32,
+        flags: IoUringEnterFlags,
+        arg: &WaitArg<'_>,
+    ) -> Result<WaitStatus> {
+        let flags = flags | IoUringEnterFlags::GETEVENTS;
+        match self.enter_arg(to_submit, min_complete, flags, arg) {
+            Ok(_) => Ok(WaitStatus::Ready),
+            Err(err) if err.raw_os_error() == Some(Errno::TIME.raw_os_error()) => {
+                Ok(WaitStatus::TimedOut)
+            },
+            Err(err) => Err(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+    use crate::{
+        completion::entry::Cqe16, shared::macros::test_uring, submission::entry::Sqe64,
+        uring::mode::Sqpoll,
+    };
+
+    #[test]
+    fn test_enter_arg_timeout() {
+        test_uring!(uring = Sqpoll::new::<Sqe64, Cqe16>(8));
+
+        let start = Instant::now();
+        let arg = WaitArg::new().timeout(Duration::from_millis(10));
+        let err = uring.enter.enter_arg(0, 1, IoUringEnterFlags::GETEVENTS, &arg).unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(Errno::TIME.raw_os_error()));
+        assert!(start.elapsed() >= Duration::from_millis(10));
+
+        let deadline = uring.enter.deadline(Duration::from_millis(10)).unwrap();
+        let arg = WaitArg::new().deadline(deadline);
+        let err = uring.enter.enter_arg(0, 1, IoUringEnterFlags::GETEVENTS, &arg).unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(Errno::TIME.raw_os_error()));
+    }
+}